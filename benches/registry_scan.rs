@@ -0,0 +1,64 @@
+//! Vergleicht einen Registry-Scan ohne Cache (kalt) mit einem Scan über
+//! einen bereits befüllten Cache (warm).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bundle::{BundleRegistry, SearchRoot, SearchScope};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const BUNDLE_COUNT: usize = 200;
+
+const INFO_JSON: &str = r#"{
+    "name": "Bench",
+    "identifier": "org.example.bench.IDX",
+    "entry_point": "bin/bench",
+    "metadata": { "category": "bench" },
+    "icons": { "icon_16": "16.png", "icon_32": "32.png", "icon_128": "128.png", "launch_screen": "launch.png" },
+    "platforms": ["linux"],
+    "minimum_system_version": "1.0",
+    "device_family": ["pi"],
+    "entitlements": ["camera", "microphone"],
+    "url_schemes": [{ "scheme": "benchIDX", "description": "Bench" }],
+    "app_services": { "background_modes": [] },
+    "security": {
+        "app_sandbox": true,
+        "app_transport_security": { "allows_insecure_http": false, "exception_domains": {} },
+        "code_signature": { "team_id": "BENCH", "entitlements_file": "" }
+    },
+    "fibyos": { "document_types": [{ "name": "Bench", "extensions": ["bench"], "icon_file": "doc.png" }] }
+}"#;
+
+fn create_bundles(root: &Path) {
+    for i in 0..BUNDLE_COUNT {
+        let content = root.join(format!("Bench{}.appd/Content", i));
+        fs::create_dir_all(&content).unwrap();
+        fs::write(content.join("Config.json"), "{}").unwrap();
+        fs::write(content.join("Info.json"), INFO_JSON.replace("IDX", &i.to_string())).unwrap();
+    }
+}
+
+fn registry_scan(c: &mut Criterion) {
+    let dir: PathBuf = std::env::temp_dir().join(format!("bundle-bench-{}", std::process::id()));
+    let root = dir.join("bundles");
+    let cache = dir.join("registry.json");
+    create_bundles(&root);
+    let roots = vec![SearchRoot::new(SearchScope::System, &root)];
+
+    let mut group = c.benchmark_group("registry_scan");
+    group.bench_function("cold", |b| {
+        b.iter(|| {
+            let _ = fs::remove_file(&cache);
+            BundleRegistry::scan_cached(roots.clone(), &cache)
+        })
+    });
+
+    BundleRegistry::scan_cached(roots.clone(), &cache);
+    group.bench_function("warm", |b| b.iter(|| BundleRegistry::scan_cached(roots.clone(), &cache)));
+    group.finish();
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+criterion_group!(benches, registry_scan);
+criterion_main!(benches);
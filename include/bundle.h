@@ -0,0 +1,240 @@
+#ifndef BUNDLE_H
+#define BUNDLE_H
+
+/* Generated by cbindgen from src/ffi. Do not edit by hand. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// Stabile numerische Fehlercodes. Die Werte dürfen sich nicht ändern.
+typedef enum BundleErrorCode {
+  // Kein Fehler.
+  BUNDLE_ERROR_CODE_OK = 0,
+  // `BundleError::NotFound`
+  BUNDLE_ERROR_CODE_NOT_FOUND = 1,
+  // `BundleError::InvalidFormat`
+  BUNDLE_ERROR_CODE_INVALID_FORMAT = 2,
+  // `BundleError::IoError`
+  BUNDLE_ERROR_CODE_IO_ERROR = 3,
+  // `BundleError::NotLoaded`
+  BUNDLE_ERROR_CODE_NOT_LOADED = 4,
+  // `BundleValidationError::MissingField`
+  BUNDLE_ERROR_CODE_VALIDATION_MISSING_FIELD = 5,
+  // `BundleValidationError::InvalidFormat`
+  BUNDLE_ERROR_CODE_VALIDATION_INVALID_FORMAT = 6,
+  // `BundleValidationError::ConfigLoadError`
+  BUNDLE_ERROR_CODE_VALIDATION_CONFIG_LOAD = 7,
+  // Ungültiges Argument (Nullzeiger, Index außerhalb des Bereichs).
+  BUNDLE_ERROR_CODE_INVALID_ARGUMENT = 8,
+} BundleErrorCode;
+
+// Auswahl eines Icons aus dem `icons`-Abschnitt der Info.json.
+typedef enum BundleIconKind {
+  BUNDLE_ICON_KIND_ICON16,
+  BUNDLE_ICON_KIND_ICON32,
+  BUNDLE_ICON_KIND_ICON128,
+  BUNDLE_ICON_KIND_LAUNCH_SCREEN,
+} BundleIconKind;
+
+// Opaker Handle auf eine geparste Info.json.
+typedef struct BundleInfo BundleInfo;
+
+// Iterator über die `metadata`-Einträge eines [`BundleInfo`].
+// Der Iterator hält eine eigene Kopie und darf den Handle überleben.
+typedef struct BundleMetadataIter BundleMetadataIter;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Prüft, ob `path` ein gültiges Bundle ist (App, Toolset, Service oder Framework).
+// Ein Nullzeiger ergibt `false` und einen `InvalidArgument`-Fehler.
+//
+// # Safety
+// `path` muss null sein oder auf einen gültigen, nullterminierten String zeigen.
+bool bundle_is_bundle_at_path(const char *path);
+
+// Sucht das äußerste Bundle oberhalb von `exe_path`.
+// Gibt den Bundle-Pfad als neuen String zurück oder null, falls keins gefunden
+// wurde (Fehlercode `NotFound`).
+// Der Rückgabewert muss mit [`bundle_string_free`] freigegeben werden.
+//
+// # Safety
+// `exe_path` muss null sein oder auf einen gültigen, nullterminierten String zeigen.
+char *bundle_find_bundle_root(const char *exe_path);
+
+// Gibt den Pfad des Bundles zurück, aus dem das laufende Programm gestartet wurde,
+// oder null, falls es nicht aus einem Bundle stammt oder ein Fehler aufgetreten ist.
+// Der Rückgabewert muss mit [`bundle_string_free`] freigegeben werden.
+char *bundle_get_current_launched_bundle_path(void);
+
+// Prüft, ob das laufende Programm aus einem Bundle gestartet wurde.
+// Gibt `1` (ja), `0` (nein) oder `-1` bei einem Fehler zurück.
+int bundle_is_launched_from_bundle(void);
+
+// Lädt die Info.json des aktuellen Bundles in das prozessweite Singleton.
+// Gibt `0` bei Erfolg und `-1` bei einem Fehler zurück (auch wenn bereits geladen).
+int bundle_load_info_config(void);
+
+// Validiert das aktuelle Bundle. Setzt voraus, dass die Info.json bereits mit
+// [`bundle_load_info_config`] geladen wurde.
+// Gibt `1` (gültig), `0` (ungültig) oder `-1` bei einem Fehler zurück.
+int bundle_validate_bundle(void);
+
+// Gibt einen von dieser Bibliothek zurückgegebenen String frei. Null wird ignoriert.
+//
+// # Safety
+// `s` muss null sein oder von einer `bundle_*`-Funktion stammen und darf
+// nicht bereits freigegeben worden sein.
+void bundle_string_free(char *s);
+
+// Code des letzten Fehlers im aktuellen Thread, oder `Ok`, falls noch keiner auftrat.
+enum BundleErrorCode bundle_last_error_code(void);
+
+// Meldung des letzten Fehlers im aktuellen Thread, oder null, falls noch keiner auftrat.
+// Der Zeiger gehört der Bibliothek und bleibt bis zum nächsten Fehler oder
+// [`bundle_clear_last_error`] im selben Thread gültig. Nicht freigeben.
+const char *bundle_last_error_message(void);
+
+// Setzt den Fehlerzustand des aktuellen Threads zurück.
+void bundle_clear_last_error(void);
+
+// Lädt `Content/Info.json` aus dem Bundle unter `bundle_path`.
+// Gibt null zurück, wenn die Datei fehlt oder nicht geparst werden kann.
+//
+// # Safety
+// `bundle_path` muss null sein oder auf einen gültigen, nullterminierten String zeigen.
+struct BundleInfo *bundle_info_open(const char *bundle_path);
+
+// Gibt eine Kopie der mit `bundle_load_info_config` geladenen Konfiguration zurück,
+// oder null, falls noch keine geladen wurde.
+struct BundleInfo *bundle_info_get_loaded(void);
+
+// Gibt einen Handle frei. Null wird ignoriert.
+//
+// # Safety
+// `info` muss null sein oder von `bundle_info_open`/`bundle_info_get_loaded` stammen
+// und darf nicht bereits freigegeben worden sein.
+void bundle_info_free(struct BundleInfo *info);
+
+// Anzeigename des Bundles.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_name(const struct BundleInfo *info);
+
+// Eindeutiger Bezeichner des Bundles.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_identifier(const struct BundleInfo *info);
+
+// Einstiegspunkt (Executable) relativ zu `Content/`.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_entry_point(const struct BundleInfo *info);
+
+// Dateiname des gewünschten Icons.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_icon(const struct BundleInfo *info, enum BundleIconKind kind);
+
+// Anzahl der deklarierten URL-Schemes.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+size_t bundle_info_url_scheme_count(const struct BundleInfo *info);
+
+// Name des URL-Schemes an Position `index`.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_url_scheme_scheme(const struct BundleInfo *info, size_t index);
+
+// Beschreibung des URL-Schemes an Position `index`.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_url_scheme_description(const struct BundleInfo *info, size_t index);
+
+// Anzahl der deklarierten Dokumenttypen.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+size_t bundle_info_document_type_count(const struct BundleInfo *info);
+
+// Name des Dokumenttyps an Position `index`.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_document_type_name(const struct BundleInfo *info, size_t index);
+
+// Icon-Datei des Dokumenttyps an Position `index`.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_document_type_icon_file(const struct BundleInfo *info, size_t index);
+
+// Anzahl der Dateiendungen des Dokumenttyps an Position `index`.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+size_t bundle_info_document_type_extension_count(const struct BundleInfo *info, size_t index);
+
+// Dateiendung `ext_index` des Dokumenttyps an Position `index`.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_document_type_extension(const struct BundleInfo *info,
+                                          size_t index,
+                                          size_t ext_index);
+
+// Anzahl der deklarierten Entitlements.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+size_t bundle_info_entitlement_count(const struct BundleInfo *info);
+
+// Entitlement an Position `index` in seiner String-Form (z.B. `"camera"`).
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+char *bundle_info_entitlement(const struct BundleInfo *info, size_t index);
+
+// Erzeugt einen Iterator über die `metadata`-Einträge.
+// Muss mit [`bundle_metadata_iter_free`] freigegeben werden.
+//
+// # Safety
+// `info` muss null oder ein gültiger Handle sein.
+struct BundleMetadataIter *bundle_info_metadata_iter(const struct BundleInfo *info);
+
+// Liefert den nächsten Eintrag. Bei `true` werden `key` und `value` mit neuen
+// Strings befüllt, die der Aufrufer freigibt.
+//
+// `false` bedeutet entweder, dass der Iterator erschöpft ist, oder einen Fehler
+// (Nullzeiger, Eintrag mit Nullbyte), der wie bei allen Funktionen als letzter
+// Fehler hinterlegt wird. Zur Unterscheidung vor der Schleife
+// [`bundle_clear_last_error`](super::bundle_clear_last_error) aufrufen und nach
+// `false` [`bundle_last_error_code`](super::bundle_last_error_code) prüfen.
+// Ein fehlerhafter Eintrag wird übersprungen; der Iterator bleibt verwendbar.
+//
+// # Safety
+// `iter` muss null oder ein gültiger Iterator sein; `key` und `value` müssen null sein
+// oder auf beschreibbaren Speicher für einen Zeiger zeigen.
+bool bundle_metadata_iter_next(struct BundleMetadataIter *iter, char **key, char **value);
+
+// Gibt einen Metadaten-Iterator frei. Null wird ignoriert.
+//
+// # Safety
+// `iter` muss null sein oder von [`bundle_info_metadata_iter`] stammen
+// und darf nicht bereits freigegeben worden sein.
+void bundle_metadata_iter_free(struct BundleMetadataIter *iter);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* BUNDLE_H */
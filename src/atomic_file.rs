@@ -0,0 +1,41 @@
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+
+/// Temporärer Name neben `path`: `.<name>.<pid>.tmp`. Versteckt, damit er in
+/// überwachten Verzeichnissen (z.B. dem Shim-Verzeichnis) nicht auffällt.
+fn temporary_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()))
+}
+
+/// Führt `write` für den temporären Pfad aus und benennt ihn danach in `path`
+/// um. Im Fehlerfall wird die temporäre Datei entfernt.
+fn replace_with(path: &Path, write: impl FnOnce(&Path) -> std::io::Result<()>) -> std::io::Result<()> {
+    let tmp = temporary_path(path);
+    let _ = fs::remove_file(&tmp);
+    let result = write(&tmp).and_then(|()| fs::rename(&tmp, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Schreibt `data` atomar nach `path`: Leser sehen entweder die alte oder die
+/// vollständige neue Datei. `permissions` werden vor dem Umbenennen gesetzt.
+pub(crate) fn write_atomically(path: &Path, data: &[u8], permissions: Option<fs::Permissions>) -> std::io::Result<()> {
+    replace_with(path, |tmp| {
+        let mut file = fs::File::create(tmp)?;
+        file.write_all(data)?;
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.sync_all()
+    })
+}
+
+/// Ersetzt `path` atomar durch einen Symlink auf `target`.
+pub(crate) fn symlink_atomically(path: &Path, target: &Path) -> std::io::Result<()> {
+    replace_with(path, |tmp| symlink(target, tmp))
+}
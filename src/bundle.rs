@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+
+use crate::bundle_load_info_config::load_bundle_info_file;
+use crate::localized_strings::StringTable;
+use crate::{
+    find_bundle_root, get_current_launched_bundle_path, localization_chain, preferred_languages,
+    is_app_bundle_dir, is_framework_bundle_dir, is_service_bundle_dir, is_toolset_bundle_dir,
+    BundleError, BundleInfoConfigFile,
+};
+
+/// Art eines Bundles, abgeleitet aus der Verzeichnisendung.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleKind {
+    /// `.appd`
+    App,
+    /// `.serviced`
+    Service,
+    /// `.toolsetd`
+    Toolset,
+    /// `.frameworkd`
+    Framework,
+}
+
+impl BundleKind {
+    /// Alle bekannten Bundle-Arten.
+    pub const ALL: [BundleKind; 4] = [
+        BundleKind::App,
+        BundleKind::Service,
+        BundleKind::Toolset,
+        BundleKind::Framework,
+    ];
+
+    /// Verzeichnisendung dieser Bundle-Art (ohne Punkt).
+    pub fn extension(&self) -> &'static str {
+        match self {
+            BundleKind::App => "appd",
+            BundleKind::Service => "serviced",
+            BundleKind::Toolset => "toolsetd",
+            BundleKind::Framework => "frameworkd",
+        }
+    }
+
+    /// Ermittelt die Bundle-Art zu einer Verzeichnisendung (ohne Punkt).
+    pub fn from_extension(ext: &str) -> Option<BundleKind> {
+        BundleKind::ALL.into_iter().find(|kind| kind.extension() == ext)
+    }
+
+    /// Ermittelt die Art des Bundles unter `path`.
+    /// Gibt `None` zurück, wenn der Pfad kein gültiges Bundle ist.
+    pub fn detect(path: &Path) -> Option<BundleKind> {
+        if is_app_bundle_dir(path) {
+            Some(BundleKind::App)
+        } else if is_toolset_bundle_dir(path) {
+            Some(BundleKind::Toolset)
+        } else if is_service_bundle_dir(path) {
+            Some(BundleKind::Service)
+        } else if is_framework_bundle_dir(path) {
+            Some(BundleKind::Framework)
+        } else {
+            None
+        }
+    }
+}
+
+/// Typisierter Handle auf ein Bundle im Dateisystem.
+///
+/// Enthält die Bundle-Art und den kanonischen Wurzelpfad. Die Info.json wird
+/// erst beim ersten Zugriff über [`Bundle::info`] geladen und danach zwischengespeichert.
+#[derive(Debug)]
+pub struct Bundle {
+    kind: BundleKind,
+    path: PathBuf,
+    info: OnceCell<BundleInfoConfigFile>,
+    /// Bereits gelesene String-Tabellen, nach Dateipfad; `None` für fehlende Tabellen.
+    pub(crate) string_tables: Mutex<HashMap<PathBuf, Option<Arc<StringTable>>>>,
+}
+
+impl Bundle {
+    /// Öffnet das Bundle unter `path`.
+    /// Gibt `BundleError::NotFound` zurück, wenn der Pfad kein gültiges Bundle ist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Bundle, BundleError> {
+        let path = path.as_ref();
+        let kind = BundleKind::detect(path).ok_or_else(|| {
+            BundleError::NotFound(format!("no bundle at '{}'", path.display()))
+        })?;
+        let path = path.canonicalize()?;
+        Ok(Bundle {
+            kind,
+            path,
+            info: OnceCell::new(),
+            string_tables: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Öffnet das äußerste Bundle, in dem `path` liegt.
+    pub fn containing(path: impl AsRef<Path>) -> Result<Bundle, BundleError> {
+        let path = path.as_ref();
+        let root = find_bundle_root(path).ok_or_else(|| {
+            BundleError::NotFound(format!("no bundle above '{}'", path.display()))
+        })?;
+        Bundle::open(root)
+    }
+
+    /// Öffnet das Bundle, aus dem das laufende Programm gestartet wurde.
+    pub fn main() -> Result<Bundle, BundleError> {
+        match get_current_launched_bundle_path() {
+            Ok(Some(path)) => Bundle::open(path),
+            Ok(None) => Err(BundleError::NotFound(
+                "program was not launched from a bundle".to_string(),
+            )),
+            Err(e) => Err(BundleError::InvalidFormat(format!("cannot determine bundle root: {:?}", e))),
+        }
+    }
+
+    /// Art des Bundles.
+    pub fn kind(&self) -> BundleKind {
+        self.kind
+    }
+
+    /// Kanonischer Wurzelpfad des Bundles.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Pfad zum `Content/`-Verzeichnis des Bundles.
+    pub fn content_path(&self) -> PathBuf {
+        self.path.join("Content")
+    }
+
+    /// Geparste `Content/Info.json`, beim ersten Aufruf geladen.
+    pub fn info(&self) -> Result<&BundleInfoConfigFile, BundleError> {
+        self.info.get_or_try_init(|| load_bundle_info_file(&self.path))
+    }
+
+    /// Sprachen, in denen lokalisierte Inhalte dieses Bundles gesucht werden,
+    /// in absteigender Priorität. Endet mit der `development_language` aus der
+    /// Info.json, sofern diese gesetzt ist und geladen werden kann.
+    pub fn preferred_localizations(&self) -> Vec<String> {
+        let development_language = self.info().ok().and_then(|info| info.development_language.as_deref());
+        localization_chain(&preferred_languages(), development_language)
+    }
+
+    /// Bezeichner aus der Info.json.
+    pub fn identifier(&self) -> Result<&str, BundleError> {
+        Ok(&self.info()?.identifier)
+    }
+}
@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::entitlements::EntitlementType;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BundleInfoConfigFile {
     pub name: String,
     pub identifier: String,
@@ -16,6 +16,10 @@ pub struct BundleInfoConfigFile {
     pub minimum_system_version: String,
     pub device_family: Vec<String>,
 
+    /// Sprache, in der das Bundle entwickelt wurde; letzte Stufe der Sprach-Fallback-Kette.
+    #[serde(default)]
+    pub development_language: Option<String>,
+
     pub entitlements: Vec<EntitlementType>,
 
     pub url_schemes: Vec<UrlScheme>,
@@ -25,9 +29,21 @@ pub struct BundleInfoConfigFile {
     pub security: Security,
 
     pub fibyos: Fibyos,
+
+    /// Voreingestellte Argumente, Umgebung und Arbeitsverzeichnis für den Start.
+    #[serde(default)]
+    pub launch: LaunchConfig,
+
+    /// Zusätzliche benannte Executables (Toolset-Befehle, Hilfsprogramme).
+    #[serde(default)]
+    pub executables: HashMap<String, ExecutableEntry>,
+
+    /// Überwachung des Dienstes; nur für Service-Bundles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<ServiceConfig>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Icons {
     #[serde(rename = "icon_16")]
     pub icon_16: String,
@@ -38,51 +54,219 @@ pub struct Icons {
     pub launch_screen: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UrlScheme {
     pub scheme: String,
     pub description: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppServices {
     pub background_modes: Vec<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Security {
     pub app_sandbox: bool,
     pub app_transport_security: AppTransportSecurity,
     pub code_signature: CodeSignature,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppTransportSecurity {
     pub allows_insecure_http: bool,
     pub exception_domains: HashMap<String, ExceptionDomain>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExceptionDomain {
     pub includes_subdomains: bool,
     pub allows_insecure_http: bool,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CodeSignature {
     pub team_id: String,
     pub entitlements_file: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Abschnitt `launch`. Alle Werte dürfen Platzhalter wie `${BUNDLE_PATH}` enthalten.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LaunchConfig {
+    /// Argumente vor den beim Start übergebenen.
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub environment: HashMap<String, String>,
+    /// Relativ zu `Content/`, falls kein absoluter Pfad. Standard ist `Content/`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+}
+
+/// Eintrag in `executables`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExecutableEntry {
+    /// Pfad relativ zu `Content/`.
+    pub path: String,
+    #[serde(default)]
+    pub description: String,
+    /// Teilmenge der Bundle-Entitlements; ohne Angabe gelten alle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entitlements: Option<Vec<EntitlementType>>,
+}
+
+/// Abschnitt `service`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServiceConfig {
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    /// Wartezeit vor einem Neustart.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart_delay_seconds: Option<u64>,
+    /// Maximale Startdauer, bevor der Start als fehlgeschlagen gilt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_timeout_seconds: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheck>,
+    /// Bezeichner von Diensten, die vorher gestartet werden, falls sie ebenfalls starten.
+    #[serde(default)]
+    pub after: Vec<String>,
+    /// Bezeichner von Diensten, die mitgestartet werden und vorher laufen müssen.
+    #[serde(default)]
+    pub requires: Vec<String>,
+    /// Sockets, auf denen der Dienst bei Bedarf gestartet wird.
+    #[serde(default)]
+    pub sockets: Vec<ServiceSocket>,
+}
+
+/// Ein Socket für die Socket-Aktivierung: entweder ein Unix-Socket (`path`)
+/// oder ein TCP-Port (`port`, optional mit `address`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServiceSocket {
+    /// Name, unter dem der Dienst den Deskriptor wiederfindet.
+    pub name: String,
+    /// Absoluter Pfad; Platzhalter wie in `launch`, außer den Daten-, Cache-
+    /// und Konfigurationsverzeichnissen (siehe [`SOCKET_PATH_FORBIDDEN_PLACEHOLDERS`](crate::SOCKET_PATH_FORBIDDEN_PLACEHOLDERS)).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    /// Adresse für `port`; ohne Angabe alle Adressen (IPv6 und IPv4).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    Never,
+    #[default]
+    OnFailure,
+    Always,
+}
+
+/// Prüfung, ob ein laufender Dienst gesund ist: entweder ein Befehl, der mit
+/// Status 0 endet, oder ein Socket, der Verbindungen annimmt.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HealthCheck {
+    /// Programm (innerhalb von `Content/`) und Argumente; Platzhalter wie in `launch`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub command: Vec<String>,
+    /// Absoluter Unix-Socket-Pfad oder `host:port`; Platzhalter wie in `launch`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub socket: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Fibyos {
     #[serde(rename = "document_types")]
     pub document_types: Vec<DocumentType>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DocumentType {
     pub name: String,
     pub extensions: Vec<String>,
     pub icon_file: String,
-}
\ No newline at end of file
+    /// MIME-Typen dieses Dokumenttyps, z.B. `text/markdown`.
+    #[serde(default)]
+    pub mime_types: Vec<String>,
+    /// MIME-Typen, zu denen dieser Typ konform ist, z.B. `text/plain` für Markdown.
+    #[serde(default)]
+    pub conforms_to: Vec<String>,
+    /// Magic-Byte-Signaturen zur Erkennung am Dateiinhalt.
+    #[serde(default)]
+    pub signatures: Vec<MagicSignature>,
+}
+
+/// Byte-Folge an einer festen Position im Dateikopf.
+/// Angegeben entweder als `hex` (`"89504e47"`) oder als `text` (`"%PDF-"`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MagicSignature {
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+impl MagicSignature {
+    /// Die zu vergleichenden Bytes, oder `None` bei fehlender oder ungültiger Angabe.
+    pub fn bytes(&self) -> Option<Vec<u8>> {
+        if let Some(text) = &self.text {
+            return Some(text.as_bytes().to_vec());
+        }
+        let hex = self.hex.as_deref()?.replace(' ', "");
+        if hex.is_empty() || hex.len() % 2 != 0 {
+            return None;
+        }
+        hex.as_bytes()
+            .chunks(2)
+            .map(|pair| Some((hex_digit(pair[0])? << 4) | hex_digit(pair[1])?))
+            .collect()
+    }
+
+    /// Prüft, ob `header` die Signatur an der angegebenen Position enthält.
+    pub fn matches(&self, header: &[u8]) -> bool {
+        self.bytes().is_some_and(|bytes| {
+            let Some(end) = self.offset.checked_add(bytes.len()) else {
+                return false;
+            };
+            header.get(self.offset..end) == Some(bytes.as_slice())
+        })
+    }
+}
+
+/// Wert einer ASCII-Hexziffer; andere Bytes (auch Teile von UTF-8-Zeichen) ergeben `None`.
+fn hex_digit(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(hex: &str, offset: usize) -> MagicSignature {
+        MagicSignature { offset, hex: Some(hex.into()), text: None }
+    }
+
+    #[test]
+    fn magic_hex_is_parsed_bytewise() {
+        assert_eq!(hex("7f 45 4C 46", 0).bytes(), Some(vec![0x7f, 0x45, 0x4c, 0x46]));
+        assert_eq!(hex("7", 0).bytes(), None);
+        assert_eq!(hex("zz", 0).bytes(), None);
+        assert_eq!(hex("+1", 0).bytes(), None);
+        // Zwei Bytes, aber kein ASCII: darf nicht mitten im Zeichen schneiden
+        assert_eq!(hex("ä", 0).bytes(), None);
+        assert_eq!(hex("aä0", 0).bytes(), None);
+    }
+
+    #[test]
+    fn magic_offset_overflow_does_not_match() {
+        assert!(hex("45", 1).matches(b"\x7fELF"));
+        assert!(!hex("45", usize::MAX).matches(b"\x7fELF"));
+        assert!(!hex("4546", 3).matches(b"\x7fELF"));
+    }
+}
@@ -1,7 +1,6 @@
 // Importiere Funktionen, Typen und Traits aus dem crate
 use once_cell::sync::OnceCell;   // Für lazy, threadsichere Initialisierung (Singleton)
 use std::path::Path;             // Für Dateipfade
-use serde_json;
 use crate::{get_current_launched_bundle_path, BundleError, BundleInfoConfigFile};                  // Zum Parsen von JSON
 
 // Singleton-Instanz für BundleInfoConfigFile, die einmalig initialisiert wird
@@ -16,7 +15,7 @@ impl From<std::io::Error> for BundleError {
 
 // Lädt und parst die Datei Content/Info.json im angegebenen Verzeichnis.
 // Liefert bei Erfolg das BundleInfoConfigFile, sonst einen passenden BundleError.
-fn load_bundle_info_file(path: &Path) -> Result<BundleInfoConfigFile, BundleError> {
+pub(crate) fn load_bundle_info_file(path: &Path) -> Result<BundleInfoConfigFile, BundleError> {
     // Erzeuge vollständigen Pfad zu Content/Info.json
     let info_json_path = path.join("Content/Info.json");
 
@@ -57,7 +56,7 @@ pub fn bundle_load_info_config() -> Result<&'static BundleInfoConfigFile, Bundle
                 format!("Fehler beim Bundle-Root: {:?}", e)
             )),
         };
-        let bundle_config = load_bundle_info_file(&current_bundle_path)?;
+        let bundle_config = load_bundle_info_file(current_bundle_path)?;
         Ok(MAIN_BUNDLE_INSTANCE.get_or_init(|| bundle_config))
     })
 }
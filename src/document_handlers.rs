@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+use crate::handler_preferences::normalize_extension;
+use crate::{BundleRegistry, DocumentType, HandlerPreferences, RegisteredBundle};
+
+/// Mögliche Endungen eines Dateinamens, die längste zuerst:
+/// `archiv.tar.gz` → `["tar.gz", "gz"]`. Ein führender Punkt (versteckte Datei)
+/// zählt nicht als Endung.
+pub fn file_extensions(path: &Path) -> Vec<String> {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let name = name.trim_start_matches('.').to_lowercase();
+    name.match_indices('.')
+        .map(|(i, _)| name[i + 1..].to_string())
+        .filter(|ext| !ext.is_empty())
+        .collect()
+}
+
+/// Ein Bundle, das eine Datei öffnen kann.
+#[derive(Debug, Clone)]
+pub struct DocumentHandler<'a> {
+    pub bundle: &'a RegisteredBundle,
+    /// Der Dokumenttyp, über den das Bundle die Datei öffnet.
+    pub document_type: &'a DocumentType,
+    /// Die übereinstimmende Endung, kleingeschrieben und ohne Punkt.
+    pub extension: String,
+}
+
+impl DocumentHandler<'_> {
+    /// Anzeigename des Dokumenttyps.
+    pub fn display_name(&self) -> &str {
+        &self.document_type.name
+    }
+
+    /// Icon-Datei des Dokumenttyps, wie in der Info.json angegeben.
+    pub fn icon_file(&self) -> &str {
+        &self.document_type.icon_file
+    }
+
+    /// Pfad zum Icon, aufgelöst über die Ressourcensuche des Bundles.
+    pub fn icon_path(&self) -> Option<PathBuf> {
+        self.bundle.open().ok()?.path_for_resource(&self.document_type.icon_file, None, None)
+    }
+}
+
+/// Sucht den Dokumenttyp von `bundle` mit der spezifischsten passenden Endung.
+fn best_match<'a>(bundle: &'a RegisteredBundle, extensions: &[String]) -> Option<(&'a DocumentType, usize)> {
+    extensions.iter().enumerate().find_map(|(rank, ext)| {
+        bundle
+            .info
+            .fibyos
+            .document_types
+            .iter()
+            .find(|doc| doc.extensions.iter().any(|e| normalize_extension(e) == *ext))
+            .map(|doc| (doc, rank))
+    })
+}
+
+impl BundleRegistry {
+    /// Alle wirksamen Bundles, die die Datei `path` anhand ihrer Endung öffnen können.
+    ///
+    /// Der Vergleich ignoriert Groß-/Kleinschreibung; mehrteilige Endungen wie
+    /// `.tar.gz` haben Vorrang vor `.gz`. Rangfolge:
+    /// 1. der in `preferences` gewählte Handler für die spezifischste Endung, zu
+    ///    der ein Handler hinterlegt ist,
+    /// 2. Bundles mit spezifischerer Endung,
+    /// 3. höhere Suchstufe, dann alphabetisch nach Bezeichner.
+    pub fn handlers_for_file(&self, path: &Path, preferences: &HandlerPreferences) -> Vec<DocumentHandler<'_>> {
+        let extensions = file_extensions(path);
+        let mut ranked: Vec<(usize, DocumentHandler<'_>)> = self
+            .bundles()
+            .filter_map(|bundle| {
+                let (document_type, rank) = best_match(bundle, &extensions)?;
+                let extension = extensions[rank].clone();
+                Some((rank, DocumentHandler { bundle, document_type, extension }))
+            })
+            .collect();
+
+        ranked.sort_by(|(rank_a, a), (rank_b, b)| {
+            rank_a
+                .cmp(rank_b)
+                .then_with(|| b.bundle.scope.cmp(&a.bundle.scope))
+                .then_with(|| a.bundle.identifier().cmp(b.bundle.identifier()))
+        });
+        let mut handlers: Vec<DocumentHandler<'_>> = ranked.into_iter().map(|(_, handler)| handler).collect();
+
+        let preferred = extensions.iter().find_map(|ext| preferences.extension_handler(ext));
+        if let Some(identifier) = preferred
+            && let Some(pos) = handlers.iter().position(|h| h.bundle.identifier() == identifier)
+        {
+            let handler = handlers.remove(pos);
+            handlers.insert(0, handler);
+        }
+        handlers
+    }
+}
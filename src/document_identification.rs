@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+use std::io::Read;
+use std::path::Path;
+
+use crate::handler_preferences::normalize_extension;
+use crate::{file_extensions, BundleError, BundleRegistry, DocumentType, RegisteredBundle};
+
+/// Anzahl der Bytes, die für die Inhaltserkennung gelesen werden.
+pub const SNIFF_HEADER_LEN: usize = 4096;
+
+/// MIME-Typ, der für Dateien mit erkennbarem Textinhalt angenommen wird.
+pub const PLAIN_TEXT_MIME_TYPE: &str = "text/plain";
+
+/// Grund, aus dem ein Dokumenttyp zu einer Datei passt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchReason {
+    /// Eine Magic-Byte-Signatur stimmt mit dem Dateikopf überein.
+    Signature,
+    /// Die Dateiendung ist deklariert.
+    Extension,
+    /// Der Typ ist ein allgemeinerer Typ, zu dem die erkannten Typen konform sind.
+    Conformance,
+}
+
+/// Ein deklarierter Dokumenttyp, der zu einer Datei passt.
+#[derive(Debug, Clone)]
+pub struct DocumentTypeMatch<'a> {
+    pub bundle: &'a RegisteredBundle,
+    pub document_type: &'a DocumentType,
+    pub reason: MatchReason,
+}
+
+/// Grobe Texterkennung: gültiges UTF-8 (ein abgeschnittenes Zeichen am Ende ist
+/// erlaubt) ohne Nullbytes.
+fn looks_like_text(header: &[u8]) -> bool {
+    if header.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(header) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+fn read_header(path: &Path) -> Result<Vec<u8>, BundleError> {
+    let mut header = Vec::with_capacity(SNIFF_HEADER_LEN);
+    std::fs::File::open(path)?
+        .take(SNIFF_HEADER_LEN as u64)
+        .read_to_end(&mut header)?;
+    Ok(header)
+}
+
+fn declares_any(document_type: &DocumentType, mime_types: &HashSet<String>) -> bool {
+    document_type
+        .mime_types
+        .iter()
+        .any(|mime| mime_types.contains(&mime.to_ascii_lowercase()))
+}
+
+impl BundleRegistry {
+    /// Alle Dokumenttypen der wirksamen Bundles, die zu `path` passen.
+    ///
+    /// Zuerst werden Signaturen gegen die ersten [`SNIFF_HEADER_LEN`] Bytes und
+    /// die Dateiendungen geprüft. Aus den MIME-Typen der Treffer (sowie
+    /// [`PLAIN_TEXT_MIME_TYPE`] für Textinhalte) wird über alle deklarierten
+    /// `conforms_to`-Angaben die transitive Hülle gebildet; Typen, die einen
+    /// dieser MIME-Typen deklarieren, passen ebenfalls. So qualifiziert sich ein
+    /// allgemeiner Text-Editor auch für Markdown-Dateien.
+    ///
+    /// Ergebnisse sind nach [`MatchReason`] sortiert; je Bundle und Dokumenttyp
+    /// gibt es höchstens einen Eintrag.
+    pub fn identify_file(&self, path: &Path) -> Result<Vec<DocumentTypeMatch<'_>>, BundleError> {
+        let header = read_header(path)?;
+        let extensions = file_extensions(path);
+        let declared: Vec<(&RegisteredBundle, &DocumentType)> = self
+            .bundles()
+            .flat_map(|bundle| bundle.info.fibyos.document_types.iter().map(move |doc| (bundle, doc)))
+            .collect();
+
+        let mut matches = Vec::new();
+        let mut unmatched = Vec::new();
+        for (bundle, document_type) in declared.iter().copied() {
+            let reason = if document_type.signatures.iter().any(|sig| sig.matches(&header)) {
+                Some(MatchReason::Signature)
+            } else if document_type
+                .extensions
+                .iter()
+                .any(|ext| extensions.contains(&normalize_extension(ext)))
+            {
+                Some(MatchReason::Extension)
+            } else {
+                None
+            };
+            match reason {
+                Some(reason) => matches.push(DocumentTypeMatch { bundle, document_type, reason }),
+                None => unmatched.push((bundle, document_type)),
+            }
+        }
+
+        // Erkannte MIME-Typen und ihre transitive Konformitätshülle
+        let mut identified: HashSet<String> = matches
+            .iter()
+            .flat_map(|m| m.document_type.mime_types.iter().map(|mime| mime.to_ascii_lowercase()))
+            .collect();
+        if !header.is_empty() && looks_like_text(&header) {
+            identified.insert(PLAIN_TEXT_MIME_TYPE.to_string());
+        }
+        loop {
+            let before = identified.len();
+            let implied: Vec<String> = declared
+                .iter()
+                .filter(|(_, doc)| declares_any(doc, &identified))
+                .flat_map(|(_, doc)| doc.conforms_to.iter().map(|mime| mime.to_ascii_lowercase()))
+                .collect();
+            identified.extend(implied);
+            if identified.len() == before {
+                break;
+            }
+        }
+
+        matches.extend(
+            unmatched
+                .into_iter()
+                .filter(|(_, doc)| declares_any(doc, &identified))
+                .map(|(bundle, document_type)| DocumentTypeMatch {
+                    bundle,
+                    document_type,
+                    reason: MatchReason::Conformance,
+                }),
+        );
+        matches.sort_by_key(|m| m.reason);
+        Ok(matches)
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::{Bundle, BundleError, BundleKind};
+
+/// Bekannte Unterverzeichnisse von `Content/`, die eingebettete Bundles enthalten,
+/// mit den dort jeweils zulässigen Bundle-Arten.
+pub const EMBEDDED_BUNDLE_DIRECTORIES: &[(&str, &[BundleKind])] = &[
+    ("Frameworks", &[BundleKind::Framework]),
+    ("Services", &[BundleKind::Service]),
+    ("Toolsets", &[BundleKind::Toolset]),
+    ("PlugIns", &[BundleKind::Framework, BundleKind::Service, BundleKind::Toolset]),
+];
+
+/// Mehrere Bundles mit demselben `identifier`.
+#[derive(Debug, Clone)]
+pub struct IdentifierCollision {
+    pub identifier: String,
+    /// Alle betroffenen Bundle-Pfade, einschließlich des enthaltenden Bundles, falls beteiligt.
+    pub paths: Vec<PathBuf>,
+}
+
+/// Ergebnis von [`Bundle::embedded_bundles`].
+#[derive(Debug, Default)]
+pub struct EmbeddedBundles {
+    /// Gefundene Bundles mit gültiger Info.json, in Suchreihenfolge.
+    pub bundles: Vec<Bundle>,
+    /// Bundles, die nicht geöffnet werden konnten oder deren Info.json fehlt
+    /// oder nicht geparst werden konnte, sowie unlesbare Einbettungsverzeichnisse.
+    pub errors: Vec<(PathBuf, BundleError)>,
+    /// Bezeichner, die von mehreren Bundles beansprucht werden.
+    pub collisions: Vec<IdentifierCollision>,
+}
+
+impl Bundle {
+    /// Listet die direkt eingebetteten Bundles in den Verzeichnissen aus
+    /// [`EMBEDDED_BUNDLE_DIRECTORIES`] auf. Verschachtelte Bundles tieferer Ebenen
+    /// werden über die zurückgegebenen Bundles selbst abgefragt.
+    ///
+    /// Über Symlinks mehrfach erreichbare Bundles werden nur einmal aufgeführt.
+    /// Bundles einer in diesem Verzeichnis unzulässigen Art werden übersprungen.
+    pub fn embedded_bundles(&self) -> Result<EmbeddedBundles, BundleError> {
+        let mut result = EmbeddedBundles::default();
+        let mut seen = HashSet::new();
+
+        for (directory, kinds) in EMBEDDED_BUNDLE_DIRECTORIES {
+            let dir = self.content_path().join(directory);
+            // Fehlende Verzeichnisse gelten als leer; unlesbare brechen die
+            // Suche in den übrigen nicht ab
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) if matches!(e.kind(), ErrorKind::NotFound | ErrorKind::NotADirectory) => continue,
+                Err(e) => {
+                    result.errors.push((dir, e.into()));
+                    continue;
+                }
+            };
+            let mut entries: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+            entries.sort();
+
+            for path in entries {
+                if !BundleKind::detect(&path).is_some_and(|kind| kinds.contains(&kind)) {
+                    continue;
+                }
+                let bundle = match Bundle::open(&path) {
+                    Ok(bundle) => bundle,
+                    Err(e) => {
+                        result.errors.push((path, e));
+                        continue;
+                    }
+                };
+                if !seen.insert(bundle.path().to_path_buf()) {
+                    continue;
+                }
+                match bundle.info() {
+                    Ok(_) => result.bundles.push(bundle),
+                    Err(e) => result.errors.push((bundle.path().to_path_buf(), e)),
+                }
+            }
+        }
+
+        result.collisions = self.identifier_collisions(&result.bundles);
+        Ok(result)
+    }
+
+    /// Sammelt Bezeichner, die mehrfach vorkommen; das enthaltende Bundle zählt mit.
+    fn identifier_collisions(&self, bundles: &[Bundle]) -> Vec<IdentifierCollision> {
+        let mut by_identifier: BTreeMap<&str, Vec<PathBuf>> = BTreeMap::new();
+        let all = std::iter::once(self).chain(bundles);
+        for bundle in all {
+            if let Ok(identifier) = bundle.identifier() {
+                by_identifier.entry(identifier).or_default().push(bundle.path().to_path_buf());
+            }
+        }
+
+        by_identifier
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .map(|(identifier, paths)| IdentifierCollision { identifier: identifier.to_string(), paths })
+            .collect()
+    }
+}
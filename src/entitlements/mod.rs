@@ -1,291 +1,227 @@
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use std::str::FromStr;
+
+use crate::BundleError;
+
+/// Erzeugt [`EntitlementType`] samt String-Zuordnung aus einer einzigen Liste
+/// `Variante => "name"`. `as_str`, `all`, `FromStr`, `Display` und Serde
+/// verwenden ausschließlich diese Liste.
+macro_rules! entitlement_types {
+    ($($(#[$meta:meta])* $variant:ident => $name:literal,)*) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum EntitlementType {
+            $($(#[$meta])* $variant,)*
+        }
+
+        impl EntitlementType {
+            /// Alle Entitlements in Deklarationsreihenfolge.
+            pub fn all() -> &'static [EntitlementType] {
+                &[$(EntitlementType::$variant,)*]
+            }
 
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(EntitlementType::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
 
-#[derive(Debug, Clone)]
-pub enum EntitlementType {
+entitlement_types! {
     // -------- Multimedia & Files ----------
 
     /// Zugriff auf die Kamera (Foto/Video-Aufnahmen, QR-Scan)
-    Camera,
+    Camera => "camera",
     /// Zugriff auf das Mikrofon (Audio-Aufnahmen, Sprachbefehle)
-    Microphone,
+    Microphone => "microphone",
     /// Zugriff auf Standort/GPS-Daten (Maps, Geofencing, Location-based Services)
-    Location,
+    Location => "location",
     /// Zugriff auf Fotos/Bildergalerie (Bilder anzeigen/hochladen/speichern)
-    Photos,
+    Photos => "photos",
     /// Zugriff auf das Dateisystem (Dateien öffnen/speichern/bearbeiten)
-    Files,
+    Files => "files",
     /// Zugriff auf die Zwischenablage (Copy/Paste von Daten)
-    Clipboard,
+    Clipboard => "clipboard",
     /// Aufnahme oder Teilen des Bildschirms (Screen Recording/Sharing)
-    ScreenRecording,
+    ScreenRecording => "screenrecording",
 
     // -------- Sensors ----------
     /// Zugriff auf Beschleunigungssensor (Schrittzähler, Bewegungserkennung)
-    Accelerometer,
+    Accelerometer => "accelerometer",
     /// Zugriff auf Gyroskop (Orientierung, Bewegungssteuerung)
-    Gyroscope,
+    Gyroscope => "gyroscope",
     /// Zugriff auf Magnetometer (Kompass, Navigation)
-    Magnetometer,
+    Magnetometer => "magnetometer",
     /// Zugriff auf Barometer (Höhenerkennung, Wetterdaten)
-    Barometer,
+    Barometer => "barometer",
     /// Zugriff auf Näherungssensor (Annäherungserkennung, Displayabschaltung)
-    ProximitySensor,
+    ProximitySensor => "proximitysensor",
     /// Zugriff auf Umgebungslichtsensor (Display-Helligkeit anpassen)
-    AmbientLightSensor,
+    AmbientLightSensor => "ambientlightsensor",
     /// Zugriff auf Temperatursensor (Gerätetemperatur, Umgebung)
-    TemperatureSensor,
+    TemperatureSensor => "temperaturesensor",
     /// Zugriff auf Luftfeuchtigkeitssensor (Klimadaten, Smart Home)
-    HumiditySensor,
+    HumiditySensor => "humiditysensor",
     /// Zugriff auf Herzfrequenzsensor (Fitness, Health-Tracking)
-    HeartRateSensor,
+    HeartRateSensor => "heartratesensor",
 
     // -------- Network & Devices ----------
     /// Firewall- und Netzwerkregelverwaltung (Ports, Zugriffssteuerung)
-    Firewall,
+    Firewall => "firewall",
     /// Allgemeiner Netzwerkzugriff (Internet, lokale Netzwerke)
-    Network,
+    Network => "network",
     /// Zugriff auf Bluetooth (Gerätesuche, -kopplung, Datenübertragung)
-    Bluetooth,
+    Bluetooth => "bluetooth",
     /// Zugriff auf USB-Geräte (Datenträger, Hardware Wallets, Peripherie)
-    Usb,
+    Usb => "usb",
     /// Zugriff auf NFC-Funktionalitäten (Kontaktloses Bezahlen, Authentifizierung)
-    Nfc,
+    Nfc => "nfc",
 
     // -------- Virtualization & Containers ----------
     /// Erstellen oder Steuern von virtuellen Maschinen (VMs)
-    VirtualMachine,
+    VirtualMachine => "virtualmachine",
     /// Nutzung und Verwaltung von Containern (z.B. Docker, App-Sandboxing)
-    Container,
+    Container => "container",
     /// Nutzung von virtuellen Dateisystemen (gemountete Dateisysteme, FUSE)
-    VirtualFileSystem,
+    VirtualFileSystem => "virtualfilesystem",
 
     // -------- System Notifications ----------
     /// Versand und Empfang von System-Benachrichtigungen
-    Notification,
+    Notification => "notification",
 
     // -------- Script & Engine Support ----------
     /// Ausführen von Python-Skripten (Automatisierung, Plugins)
-    PythonIO,
+    PythonIO => "pythonio",
     /// Ausführen von Bash-Skripten (Automatisierung, Systemintegration)
-    BashIO,
+    BashIO => "bashio",
     /// Ausführen von Windows-Anwendungen mittels Wine-Engine
-    WineEngine,
+    WineEngine => "wineengine",
 
     // -------- System Permissions ----------
     /// Zugriff auf geschützte Systemdienste (z.B. Prozesssteuerung, Power-Management)
-    SystemServices,
+    SystemServices => "systemservices",
 
     // -------- VPN Permissions ----------
     /// Betrieb als VPN-Host (VPN-Serverfunktion)
-    VpnHost,
+    VpnHost => "vpnhost",
     /// Verbindung als VPN-Client (VPN-Clientfunktion)
-    VpnClient,
+    VpnClient => "vpnclient",
 
     // -------- Crypto/Bitcoin Permissions ----------
     /// Verwalteter Zugang zu kryptographischem Speicher (Keys, Seeds, Zertifikate)
-    CryptoStore,
+    CryptoStore => "cryptostore",
     /// Lesender Zugriff auf Wallet-Daten (Adressen, Guthaben, Transaktionsverlauf)
-    WalletRead,
+    WalletRead => "walletread",
     /// Schreibender Zugriff auf Wallet (Transaktionen erstellen/senden)
-    WalletWrite,
+    WalletWrite => "walletwrite",
     /// Zugriff auf Blockchain-Daten (Blöcke, Transaktionen)
-    ChainRead,
+    ChainRead => "chainread",
     /// Blockchain-Synchronisierung, vollständige Verifizierung
-    ChainSync,
+    ChainSync => "chainsync",
     /// Zugriff auf P2P-Netzwerkfunktionen (Node Discovery, Gossip, Peer-Management)
-    P2PNetwork,
+    P2PNetwork => "p2pnetwork",
     /// Zugriff auf den Mempool (ungeminte Transaktionen lesen/schreiben)
-    MempoolAccess,
+    MempoolAccess => "mempoolaccess",
     /// Verwaltung von Lightning-Channels (öffnen, schließen, Status abfragen)
-    LightningChannels,
+    LightningChannels => "lightningchannels",
     /// Lightning-Zahlungen senden/empfangen
-    LightningPay,
+    LightningPay => "lightningpay",
     /// Lesender Zugriff auf Lightning-Netzwerk-Info (Channels, Routing)
-    LightningInfo,
+    LightningInfo => "lightninginfo",
     /// Export des Wallet-Seeds/Keys (Backup, Migration – **sehr sensitiv**)
-    KeySeedExport,
+    KeySeedExport => "keyseedexport",
     /// Zugriff auf externe Hardware Wallets (z.B. Ledger, Trezor)
-    HardwareWalletAccess,
+    HardwareWalletAccess => "hardwarewalletaccess",
     /// Lesender Zugriff auf Hardware Wallet (z.B. Adressen, Public Keys)
-    HardwareWalletRead,
+    HardwareWalletRead => "hardwarewalletread",
     /// Signatur-Funktionen auf Hardware Wallet (Transaktionen signieren)
-    HardwareWalletSign,
+    HardwareWalletSign => "hardwarewalletsign",
 
     // -------- Enterprise Permissions ----------
     // Device & App Management
     /// Administrative Steuerung des Geräts (MDM, Richtlinien setzen)
-    DeviceAdmin,
+    DeviceAdmin => "deviceadmin",
     /// Fernlöschung von Daten oder Gerät (bei Verlust, Diebstahl)
-    RemoteWipe,
+    RemoteWipe => "remotewipe",
     /// Fernkonfiguration von Einstellungen/Profilen (z.B. WLAN, VPN, App-Settings)
-    RemoteConfig,
+    RemoteConfig => "remoteconfig",
     /// Installation/Deinstallation von Apps durch die Verwaltung
-    AppInstall,
+    AppInstall => "appinstall",
 
     // Network & Connectivity
     /// Zugang zu firmeninternen VPNs und deren Verwaltung
-    EnterpriseVpn,
+    EnterpriseVpn => "enterprisevpn",
     /// Zentrale Steuerung und Einschränkung von Netzwerkzugriffen
-    NetworkPolicyControl,
+    NetworkPolicyControl => "networkpolicycontrol",
 
     // Auth & Identity
     /// Nutzung von Single Sign-On (LDAP, SAML, OIDC, Azure AD, etc.)
-    EnterpriseSSO,
+    EnterpriseSSO => "enterprisesso",
     /// Zugriff auf oder Import von Zertifikaten (TLS, E-Mail, VPN)
-    CertificateStore,
+    CertificateStore => "certificatestore",
     /// Verwaltung und Erzwingung von biometrischer Authentifizierung (Face/TouchID)
-    BiometricAdmin,
+    BiometricAdmin => "biometricadmin",
 
     // Security & DLP
     /// Steuerung von Datenabfluss (z.B. Screenshots, USB, Weiterleitungen blockieren)
-    DlpControl,
+    DlpControl => "dlpcontrol",
     /// Kontrolle/Absicherung der Zwischenablage (Clipboard) im Enterprise-Kontext
-    SecureClipboard,
+    SecureClipboard => "secureclipboard",
     /// Zugriff auf zentrale Audit- und Sicherheitsprotokolle
-    AuditLogAccess,
+    AuditLogAccess => "auditlogaccess",
     /// Auswertung der App- und Gerätenutzung (anonymisiert/aggregiert)
-    UsageStats,
+    UsageStats => "usagestats",
 
     // Updates & Support
     /// Erlaubnis, App/Client eigenständig zu updaten (Self-Update)
-    SelfUpdate,
+    SelfUpdate => "selfupdate",
     /// Remote-Support und Fernzugriff (z.B. für IT-Support, temporär und autorisiert)
-    RemoteSupport,
+    RemoteSupport => "remotesupport",
 
     // -------- Raspberry Pi / Embedded Hardware Permissions ----------
     /// Zugriff auf General Purpose Input/Output Pins (z.B. LED schalten, Sensoren auslesen)
-    GpioAccess,
+    GpioAccess => "gpioaccess",
     /// Zugriff auf I2C-Bus (Kommunikation mit z.B. Displays, Sensoren, RTC)
-    I2cAccess,
+    I2cAccess => "i2caccess",
     /// Zugriff auf SPI-Bus (z.B. für schnelle Sensoren, Flash-Speicher)
-    SpiAccess,
+    SpiAccess => "spiaccess",
     /// Zugriff auf UART/Serielle Schnittstellen (z.B. serielle Kommunikation mit Modulen)
-    UartAccess,
+    UartAccess => "uartaccess",
     /// Zugriff auf PWM-Ausgänge (z.B. für Motorsteuerung, LED-Dimmung)
-    PwmAccess,
+    PwmAccess => "pwmaccess",
     /// Zugriff auf 1-Wire-Bus (z.B. für Temperatursensoren)
-    OneWireAccess,
+    OneWireAccess => "onewireaccess",
     /// Zugriff auf CAN-Bus (z.B. für Automobil- oder Industrieanwendungen)
-    CanBusAccess,
+    CanBusAccess => "canbusaccess",
     /// Zugriff auf ADC (Analog-Digital-Converter, falls vorhanden)
-    AdcAccess,
+    AdcAccess => "adcaccess",
     /// Zugriff auf integriertes Display (z.B. Pi Touchscreen)
-    DisplayAccess,
+    DisplayAccess => "displayaccess",
     /// Zugriff auf Kamera-Modul über CSI (Camera Serial Interface)
-    PiCameraModule,
+    PiCameraModule => "picameramodule",
     /// Zugriff auf Hardware-Temperatursensor des Boards
-    BoardTemperatureAccess,
+    BoardTemperatureAccess => "boardtemperatureaccess",
     /// Zugriff auf Board-spezifische LEDs (z.B. „Power“, „ACT“ LED)
-    BoardLedAccess,
+    BoardLedAccess => "boardledaccess",
 }
 
-impl EntitlementType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            // Multimedia & Files
-            EntitlementType::Camera => "camera",
-            EntitlementType::Microphone => "microphone",
-            EntitlementType::Location => "location",
-            EntitlementType::Photos => "photos",
-            EntitlementType::Files => "files",
-            EntitlementType::Clipboard => "clipboard",
-            EntitlementType::ScreenRecording => "screenrecording",
-
-            // Sensors
-            EntitlementType::Accelerometer => "accelerometer",
-            EntitlementType::Gyroscope => "gyroscope",
-            EntitlementType::Magnetometer => "magnetometer",
-            EntitlementType::Barometer => "barometer",
-            EntitlementType::ProximitySensor => "proximitysensor",
-            EntitlementType::AmbientLightSensor => "ambientlightsensor",
-            EntitlementType::TemperatureSensor => "temperaturesensor",
-            EntitlementType::HumiditySensor => "humiditysensor",
-            EntitlementType::HeartRateSensor => "heartratesensor",
-
-            // Network & Devices
-            EntitlementType::Firewall => "firewall",
-            EntitlementType::Network => "network",
-            EntitlementType::Bluetooth => "bluetooth",
-            EntitlementType::Usb => "usb",
-            EntitlementType::Nfc => "nfc",
-
-            // Virtualization & Containers
-            EntitlementType::VirtualMachine => "virtualmachine",
-            EntitlementType::Container => "container",
-            EntitlementType::VirtualFileSystem => "virtualfilesystem",
-
-            // System Notifications
-            EntitlementType::Notification => "notification",
-
-            // Script & Engine Support
-            EntitlementType::PythonIO => "pythonio",
-            EntitlementType::BashIO => "bashio",
-            EntitlementType::WineEngine => "wineengine",
-
-            // System Permissions
-            EntitlementType::SystemServices => "systemservices",
-
-            // VPN
-            EntitlementType::VpnHost => "vpnhost",
-            EntitlementType::VpnClient => "vpnclient",
-
-            // Crypto/Bitcoin
-            EntitlementType::CryptoStore => "cryptostore",
-            EntitlementType::WalletRead => "walletread",
-            EntitlementType::WalletWrite => "walletwrite",
-            EntitlementType::ChainRead => "chainread",
-            EntitlementType::ChainSync => "chainsync",
-            EntitlementType::P2PNetwork => "p2pnetwork",
-            EntitlementType::MempoolAccess => "mempoolaccess",
-            EntitlementType::LightningChannels => "lightningchannels",
-            EntitlementType::LightningPay => "lightningpay",
-            EntitlementType::LightningInfo => "lightninginfo",
-            EntitlementType::KeySeedExport => "keyseedexport",
-            EntitlementType::HardwareWalletAccess => "hardwarewalletaccess",
-            EntitlementType::HardwareWalletRead => "hardwarewalletread",
-            EntitlementType::HardwareWalletSign => "hardwarewalletsign",
+impl FromStr for EntitlementType {
+    type Err = BundleError;
 
-            // Enterprise: Device & App Management
-            EntitlementType::DeviceAdmin => "deviceadmin",
-            EntitlementType::RemoteWipe => "remotewipe",
-            EntitlementType::RemoteConfig => "remoteconfig",
-            EntitlementType::AppInstall => "appinstall",
-
-            // Enterprise: Network & Connectivity
-            EntitlementType::EnterpriseVpn => "enterprisevpn",
-            EntitlementType::NetworkPolicyControl => "networkpolicycontrol",
-
-            // Enterprise: Auth & Identity
-            EntitlementType::EnterpriseSSO => "enterprisesso",
-            EntitlementType::CertificateStore => "certificatestore",
-            EntitlementType::BiometricAdmin => "biometricadmin",
-
-            // Enterprise: Security & DLP
-            EntitlementType::DlpControl => "dlpcontrol",
-            EntitlementType::SecureClipboard => "secureclipboard",
-            EntitlementType::AuditLogAccess => "auditlogaccess",
-            EntitlementType::UsageStats => "usagestats",
-
-            // Enterprise: Updates & Support
-            EntitlementType::SelfUpdate => "selfupdate",
-            EntitlementType::RemoteSupport => "remotesupport",
+    fn from_str(value: &str) -> Result<EntitlementType, BundleError> {
+        EntitlementType::all()
+            .iter()
+            .find(|entitlement| entitlement.as_str() == value)
+            .copied()
+            .ok_or_else(|| BundleError::InvalidFormat(format!("unknown permission type: {}", value)))
+    }
+}
 
-            // Raspberry Pi / Embedded Hardware
-            EntitlementType::GpioAccess => "gpioaccess",
-            EntitlementType::I2cAccess => "i2caccess",
-            EntitlementType::SpiAccess => "spiaccess",
-            EntitlementType::UartAccess => "uartaccess",
-            EntitlementType::PwmAccess => "pwmaccess",
-            EntitlementType::OneWireAccess => "onewireaccess",
-            EntitlementType::CanBusAccess => "canbusaccess",
-            EntitlementType::AdcAccess => "adcaccess",
-            EntitlementType::DisplayAccess => "displayaccess",
-            EntitlementType::PiCameraModule => "picameramodule",
-            EntitlementType::BoardTemperatureAccess => "boardtemperatureaccess",
-            EntitlementType::BoardLedAccess => "boardledaccess",
-        }
+impl fmt::Display for EntitlementType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
     }
 }
 
@@ -308,16 +244,20 @@ impl<'de> Deserialize<'de> for EntitlementType {
             where
                 E: serde::de::Error,
             {
-                match value {
-                    "camera" => Ok(EntitlementType::Camera),
-                    "microphone" => Ok(EntitlementType::Microphone),
-                    "location" => Ok(EntitlementType::Location),
-                    // ... weitere Mappings ...
-                    _ => Err(E::custom(format!("unknown permission type: {}", value))),
-                }
+                value.parse().map_err(|_| E::custom(format!("unknown permission type: {}", value)))
             }
         }
 
         deserializer.deserialize_str(EntitlementTypeVisitor)
     }
-}
\ No newline at end of file
+}
+
+// Serialisiert EntitlementType als seinen String-Namen
+impl Serialize for EntitlementType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
@@ -19,3 +19,35 @@ pub enum BundleValidationError {
     InvalidFormat(String),
     ConfigLoadError(String)
 }
+
+impl std::fmt::Display for BundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BundleError::NotFound(msg) => write!(f, "not found: {}", msg),
+            BundleError::InvalidFormat(msg) => write!(f, "invalid format: {}", msg),
+            BundleError::IoError(e) => write!(f, "I/O error: {}", e),
+            BundleError::NotLoaded => f.write_str("bundle info config not loaded"),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BundleError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for BundleValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BundleValidationError::MissingField(field) => write!(f, "missing field: {}", field),
+            BundleValidationError::InvalidFormat(msg) => write!(f, "invalid format: {}", msg),
+            BundleValidationError::ConfigLoadError(msg) => write!(f, "config load error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BundleValidationError {}
@@ -0,0 +1,132 @@
+use std::ffi::OsString;
+use std::path::{Component, Path, PathBuf};
+
+use crate::launch::check_executable;
+use crate::{Bundle, BundleError, BundleProcess, BundleValidationResult, EntitlementType, ExecutableEntry};
+
+/// Ein benanntes Executable aus dem Abschnitt `executables` der Info.json.
+#[derive(Debug, Clone)]
+pub struct BundleExecutable {
+    pub name: String,
+    /// Absoluter Pfad innerhalb von `Content/`.
+    pub path: PathBuf,
+    pub description: String,
+    /// Wirksame Entitlements: die angegebene Teilmenge oder alle des Bundles.
+    pub entitlements: Vec<EntitlementType>,
+}
+
+/// Prüft, ob `name` als Befehlsname taugt (kein Pfadtrenner, kein Leerraum).
+pub(crate) fn is_valid_executable_name(name: &str) -> bool {
+    !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.starts_with('-')
+        && !name.chars().any(|c| c == '/' || c.is_whitespace() || c.is_control())
+}
+
+/// Prüft, ob der relative Pfad `relative` eine Datei unterhalb seines
+/// Bezugsverzeichnisses benennt: kein `..`, nicht absolut, nicht leer.
+pub(crate) fn stays_inside(relative: &Path) -> bool {
+    relative.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        && relative.components().any(|c| matches!(c, Component::Normal(_)))
+}
+
+impl Bundle {
+    fn resolve_executable(&self, name: &str, entry: &ExecutableEntry) -> Result<BundleExecutable, BundleError> {
+        let info = self.info()?;
+        if !stays_inside(Path::new(&entry.path)) {
+            return Err(BundleError::InvalidFormat(format!(
+                "executable '{}' points outside Content/: '{}'",
+                name, entry.path
+            )));
+        }
+        Ok(BundleExecutable {
+            name: name.to_string(),
+            path: self.content_path().join(&entry.path),
+            description: entry.description.clone(),
+            entitlements: entry.entitlements.clone().unwrap_or_else(|| info.entitlements.clone()),
+        })
+    }
+
+    /// Alle deklarierten Executables, nach Namen sortiert.
+    pub fn executables(&self) -> Result<Vec<BundleExecutable>, BundleError> {
+        let mut entries: Vec<_> = self.info()?.executables.iter().collect();
+        entries.sort_by_key(|(name, _)| *name);
+        entries
+            .into_iter()
+            .map(|(name, entry)| self.resolve_executable(name, entry))
+            .collect()
+    }
+
+    /// Das Executable mit dem Namen `name`.
+    pub fn executable(&self, name: &str) -> Result<BundleExecutable, BundleError> {
+        let entry = self.info()?.executables.get(name).ok_or_else(|| {
+            BundleError::NotFound(format!("no executable '{}' in '{}'", name, self.path().display()))
+        })?;
+        self.resolve_executable(name, entry)
+    }
+
+    /// Startet das Executable `name` mit Umgebung und Arbeitsverzeichnis wie
+    /// bei [`launch`](Self::launch). Der Abschnitt `launch` gilt nur für das
+    /// Haupt-Executable und wird hier nicht angewendet.
+    pub fn launch_executable(
+        &self,
+        name: &str,
+        args: &[&str],
+        env: &[(&str, &str)],
+    ) -> Result<BundleProcess, BundleError> {
+        let executable = self.executable(name)?;
+        check_executable(&executable.path)?;
+
+        let mut command = self.command_for(executable.path)?;
+        command.args = args.iter().map(OsString::from).collect();
+        for (key, value) in env {
+            command.set_env(key, value);
+        }
+        self.create_data_directories()?;
+        command.spawn()
+    }
+
+    /// Prüft alle deklarierten Executables: gültiger Name, Pfad innerhalb von
+    /// `Content/`, vorhandenes ELF-Binary der Host-Architektur oder Skript,
+    /// Entitlements als Teilmenge der Bundle-Entitlements.
+    pub fn validate_executables(&self) -> Result<BundleValidationResult, BundleError> {
+        let info = self.info()?;
+        let mut problems = Vec::new();
+
+        let mut names: Vec<&String> = info.executables.keys().collect();
+        names.sort();
+        for name in names {
+            let entry = &info.executables[name];
+            if !is_valid_executable_name(name) {
+                problems.push(format!("invalid executable name '{}'", name));
+            }
+            if !stays_inside(Path::new(&entry.path)) {
+                problems.push(format!("executable '{}' points outside Content/: '{}'", name, entry.path));
+                continue;
+            }
+            if let Err(e) = check_executable(&self.content_path().join(&entry.path)) {
+                problems.push(format!("executable '{}': {}", name, e));
+            }
+            for entitlement in entry.entitlements.iter().flatten() {
+                if !info.entitlements.contains(entitlement) {
+                    problems.push(format!(
+                        "executable '{}' requests entitlement '{}' not granted to the bundle",
+                        name,
+                        entitlement.as_str()
+                    ));
+                }
+            }
+        }
+
+        Ok(BundleValidationResult {
+            is_valid: problems.is_empty(),
+            message: if problems.is_empty() {
+                "Executables sind gültig".to_string()
+            } else {
+                problems.join("; ")
+            },
+            warnings: Vec::new(),
+        })
+    }
+}
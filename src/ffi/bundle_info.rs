@@ -0,0 +1,336 @@
+//! Opaker `BundleInfo*`-Handle für C-Aufrufer.
+//!
+//! Ein Handle kapselt eine Kopie von [`BundleInfoConfigFile`] und bleibt gültig,
+//! bis es mit [`bundle_info_free`] freigegeben wird. Alle zurückgegebenen Strings
+//! sind Kopien und müssen mit [`bundle_string_free`](super::bundle_string_free)
+//! freigegeben werden. Ungültige Handles oder Indizes ergeben null bzw. `0` und
+//! hinterlegen einen `InvalidArgument`-Fehler.
+
+use std::ffi::c_char;
+
+use crate::bundle_load_info_config::load_bundle_info_file;
+use crate::{get_loaded_bundle_info_config, BundleInfoConfigFile};
+
+use super::{path_from_c, report, report_invalid_argument, string_into_c};
+
+/// Opaker Handle auf eine geparste Info.json.
+pub struct BundleInfo {
+    config: BundleInfoConfigFile,
+}
+
+/// Auswahl eines Icons aus dem `icons`-Abschnitt der Info.json.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub enum BundleIconKind {
+    Icon16,
+    Icon32,
+    Icon128,
+    LaunchScreen,
+}
+
+/// Iterator über die `metadata`-Einträge eines [`BundleInfo`].
+/// Der Iterator hält eine eigene Kopie und darf den Handle überleben.
+pub struct BundleMetadataIter {
+    entries: std::vec::IntoIter<(String, String)>,
+}
+
+/// Liefert die Konfiguration hinter einem Handle, sofern er nicht null ist.
+unsafe fn config<'a>(info: *const BundleInfo) -> Option<&'a BundleInfoConfigFile> {
+    let config = unsafe { info.as_ref() }.map(|info| &info.config);
+    if config.is_none() {
+        report_invalid_argument("info handle must not be null");
+    }
+    config
+}
+
+/// Liefert das Element an `index` und meldet einen Fehler, falls der Index ungültig ist.
+fn at<T>(items: &[T], index: usize) -> Option<&T> {
+    let item = items.get(index);
+    if item.is_none() {
+        report_invalid_argument(&format!("index {} out of range (len {})", index, items.len()));
+    }
+    item
+}
+
+fn str_into_c(s: &str) -> *mut c_char {
+    string_into_c(s.as_bytes())
+}
+
+fn into_handle(config: BundleInfoConfigFile) -> *mut BundleInfo {
+    Box::into_raw(Box::new(BundleInfo { config }))
+}
+
+/// Lädt `Content/Info.json` aus dem Bundle unter `bundle_path`.
+/// Gibt null zurück, wenn die Datei fehlt oder nicht geparst werden kann.
+///
+/// # Safety
+/// `bundle_path` muss null sein oder auf einen gültigen, nullterminierten String zeigen.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_open(bundle_path: *const c_char) -> *mut BundleInfo {
+    let Some(path) = (unsafe { path_from_c(bundle_path) }) else {
+        return std::ptr::null_mut();
+    };
+    match load_bundle_info_file(path) {
+        Ok(config) => into_handle(config),
+        Err(e) => {
+            report(&e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Gibt eine Kopie der mit `bundle_load_info_config` geladenen Konfiguration zurück,
+/// oder null, falls noch keine geladen wurde.
+#[unsafe(no_mangle)]
+pub extern "C" fn bundle_info_get_loaded() -> *mut BundleInfo {
+    match get_loaded_bundle_info_config() {
+        Ok(config) => into_handle(config.clone()),
+        Err(e) => {
+            report(&e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Gibt einen Handle frei. Null wird ignoriert.
+///
+/// # Safety
+/// `info` muss null sein oder von `bundle_info_open`/`bundle_info_get_loaded` stammen
+/// und darf nicht bereits freigegeben worden sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_free(info: *mut BundleInfo) {
+    if !info.is_null() {
+        drop(unsafe { Box::from_raw(info) });
+    }
+}
+
+/// Anzeigename des Bundles.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_name(info: *const BundleInfo) -> *mut c_char {
+    unsafe { config(info) }.map_or(std::ptr::null_mut(), |c| str_into_c(&c.name))
+}
+
+/// Eindeutiger Bezeichner des Bundles.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_identifier(info: *const BundleInfo) -> *mut c_char {
+    unsafe { config(info) }.map_or(std::ptr::null_mut(), |c| str_into_c(&c.identifier))
+}
+
+/// Einstiegspunkt (Executable) relativ zu `Content/`.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_entry_point(info: *const BundleInfo) -> *mut c_char {
+    unsafe { config(info) }.map_or(std::ptr::null_mut(), |c| str_into_c(&c.entry_point))
+}
+
+/// Dateiname des gewünschten Icons.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_icon(info: *const BundleInfo, kind: BundleIconKind) -> *mut c_char {
+    unsafe { config(info) }.map_or(std::ptr::null_mut(), |c| {
+        let icons = &c.icons;
+        str_into_c(match kind {
+            BundleIconKind::Icon16 => &icons.icon_16,
+            BundleIconKind::Icon32 => &icons.icon_32,
+            BundleIconKind::Icon128 => &icons.icon_128,
+            BundleIconKind::LaunchScreen => &icons.launch_screen,
+        })
+    })
+}
+
+/// Anzahl der deklarierten URL-Schemes.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_url_scheme_count(info: *const BundleInfo) -> usize {
+    unsafe { config(info) }.map_or(0, |c| c.url_schemes.len())
+}
+
+/// Name des URL-Schemes an Position `index`.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_url_scheme_scheme(info: *const BundleInfo, index: usize) -> *mut c_char {
+    unsafe { config(info) }
+        .and_then(|c| at(&c.url_schemes, index))
+        .map_or(std::ptr::null_mut(), |u| str_into_c(&u.scheme))
+}
+
+/// Beschreibung des URL-Schemes an Position `index`.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_url_scheme_description(info: *const BundleInfo, index: usize) -> *mut c_char {
+    unsafe { config(info) }
+        .and_then(|c| at(&c.url_schemes, index))
+        .map_or(std::ptr::null_mut(), |u| str_into_c(&u.description))
+}
+
+/// Anzahl der deklarierten Dokumenttypen.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_document_type_count(info: *const BundleInfo) -> usize {
+    unsafe { config(info) }.map_or(0, |c| c.fibyos.document_types.len())
+}
+
+/// Name des Dokumenttyps an Position `index`.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_document_type_name(info: *const BundleInfo, index: usize) -> *mut c_char {
+    unsafe { config(info) }
+        .and_then(|c| at(&c.fibyos.document_types, index))
+        .map_or(std::ptr::null_mut(), |d| str_into_c(&d.name))
+}
+
+/// Icon-Datei des Dokumenttyps an Position `index`.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_document_type_icon_file(info: *const BundleInfo, index: usize) -> *mut c_char {
+    unsafe { config(info) }
+        .and_then(|c| at(&c.fibyos.document_types, index))
+        .map_or(std::ptr::null_mut(), |d| str_into_c(&d.icon_file))
+}
+
+/// Anzahl der Dateiendungen des Dokumenttyps an Position `index`.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_document_type_extension_count(info: *const BundleInfo, index: usize) -> usize {
+    unsafe { config(info) }
+        .and_then(|c| at(&c.fibyos.document_types, index))
+        .map_or(0, |d| d.extensions.len())
+}
+
+/// Dateiendung `ext_index` des Dokumenttyps an Position `index`.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_document_type_extension(
+    info: *const BundleInfo,
+    index: usize,
+    ext_index: usize,
+) -> *mut c_char {
+    unsafe { config(info) }
+        .and_then(|c| at(&c.fibyos.document_types, index))
+        .and_then(|d| at(&d.extensions, ext_index))
+        .map_or(std::ptr::null_mut(), |e| str_into_c(e))
+}
+
+/// Anzahl der deklarierten Entitlements.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_entitlement_count(info: *const BundleInfo) -> usize {
+    unsafe { config(info) }.map_or(0, |c| c.entitlements.len())
+}
+
+/// Entitlement an Position `index` in seiner String-Form (z.B. `"camera"`).
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_entitlement(info: *const BundleInfo, index: usize) -> *mut c_char {
+    unsafe { config(info) }
+        .and_then(|c| at(&c.entitlements, index))
+        .map_or(std::ptr::null_mut(), |e| str_into_c(e.as_str()))
+}
+
+/// Erzeugt einen Iterator über die `metadata`-Einträge.
+/// Muss mit [`bundle_metadata_iter_free`] freigegeben werden.
+///
+/// # Safety
+/// `info` muss null oder ein gültiger Handle sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_info_metadata_iter(info: *const BundleInfo) -> *mut BundleMetadataIter {
+    let Some(config) = (unsafe { config(info) }) else {
+        return std::ptr::null_mut();
+    };
+    let mut entries: Vec<(String, String)> = config
+        .metadata
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    // Sortiert, damit C-Aufrufer eine stabile Reihenfolge sehen
+    entries.sort();
+    Box::into_raw(Box::new(BundleMetadataIter { entries: entries.into_iter() }))
+}
+
+/// Liefert den nächsten Eintrag. Bei `true` werden `key` und `value` mit neuen
+/// Strings befüllt, die der Aufrufer freigibt.
+///
+/// `false` bedeutet entweder, dass der Iterator erschöpft ist, oder einen Fehler
+/// (Nullzeiger, Eintrag mit Nullbyte), der wie bei allen Funktionen als letzter
+/// Fehler hinterlegt wird. Zur Unterscheidung vor der Schleife
+/// [`bundle_clear_last_error`](super::bundle_clear_last_error) aufrufen und nach
+/// `false` [`bundle_last_error_code`](super::bundle_last_error_code) prüfen.
+/// Ein fehlerhafter Eintrag wird übersprungen; der Iterator bleibt verwendbar.
+///
+/// # Safety
+/// `iter` muss null oder ein gültiger Iterator sein; `key` und `value` müssen null sein
+/// oder auf beschreibbaren Speicher für einen Zeiger zeigen.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_metadata_iter_next(
+    iter: *mut BundleMetadataIter,
+    key: *mut *mut c_char,
+    value: *mut *mut c_char,
+) -> bool {
+    let Some(iter) = (unsafe { iter.as_mut() }) else {
+        report_invalid_argument("iterator must not be null");
+        return false;
+    };
+    let Some((k, v)) = iter.entries.next() else {
+        return false;
+    };
+    // Beide Strings vorab erzeugen, damit bei einem Fehler nichts halb übergeben wird
+    let k = str_into_c(&k);
+    let v = if k.is_null() { std::ptr::null_mut() } else { str_into_c(&v) };
+    if k.is_null() || v.is_null() {
+        unsafe { super::bundle_string_free(k) };
+        return false;
+    }
+    if key.is_null() {
+        unsafe { super::bundle_string_free(k) };
+    } else {
+        unsafe { *key = k };
+    }
+    if value.is_null() {
+        unsafe { super::bundle_string_free(v) };
+    } else {
+        unsafe { *value = v };
+    }
+    true
+}
+
+/// Gibt einen Metadaten-Iterator frei. Null wird ignoriert.
+///
+/// # Safety
+/// `iter` muss null sein oder von [`bundle_info_metadata_iter`] stammen
+/// und darf nicht bereits freigegeben worden sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_metadata_iter_free(iter: *mut BundleMetadataIter) {
+    if !iter.is_null() {
+        drop(unsafe { Box::from_raw(iter) });
+    }
+}
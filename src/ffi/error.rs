@@ -0,0 +1,111 @@
+//! Fehlercodes und thread-lokale Fehlermeldung für C-Aufrufer.
+//!
+//! Schlägt eine exportierte Funktion fehl, signalisiert sie das über ihren
+//! Rückgabewert (null, `false`, `0` oder `-1`, je nach Funktion) und hinterlegt
+//! Code und Meldung im Stil von `errno` für den aufrufenden Thread. Erfolgreiche
+//! Aufrufe setzen den Fehlerzustand nicht zurück.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+
+use crate::{BundleError, BundleValidationError};
+
+/// Stabile numerische Fehlercodes. Die Werte dürfen sich nicht ändern.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleErrorCode {
+    /// Kein Fehler.
+    Ok = 0,
+    /// `BundleError::NotFound`
+    NotFound = 1,
+    /// `BundleError::InvalidFormat`
+    InvalidFormat = 2,
+    /// `BundleError::IoError`
+    IoError = 3,
+    /// `BundleError::NotLoaded`
+    NotLoaded = 4,
+    /// `BundleValidationError::MissingField`
+    ValidationMissingField = 5,
+    /// `BundleValidationError::InvalidFormat`
+    ValidationInvalidFormat = 6,
+    /// `BundleValidationError::ConfigLoadError`
+    ValidationConfigLoad = 7,
+    /// Ungültiges Argument (Nullzeiger, Index außerhalb des Bereichs).
+    InvalidArgument = 8,
+}
+
+impl From<&BundleError> for BundleErrorCode {
+    fn from(e: &BundleError) -> Self {
+        match e {
+            BundleError::NotFound(_) => BundleErrorCode::NotFound,
+            BundleError::InvalidFormat(_) => BundleErrorCode::InvalidFormat,
+            BundleError::IoError(_) => BundleErrorCode::IoError,
+            BundleError::NotLoaded => BundleErrorCode::NotLoaded,
+        }
+    }
+}
+
+impl From<&BundleValidationError> for BundleErrorCode {
+    fn from(e: &BundleValidationError) -> Self {
+        match e {
+            BundleValidationError::MissingField(_) => BundleErrorCode::ValidationMissingField,
+            BundleValidationError::InvalidFormat(_) => BundleErrorCode::ValidationInvalidFormat,
+            BundleValidationError::ConfigLoadError(_) => BundleErrorCode::ValidationConfigLoad,
+        }
+    }
+}
+
+struct LastError {
+    code: BundleErrorCode,
+    message: CString,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<LastError>> = const { RefCell::new(None) };
+}
+
+/// Hinterlegt Code und Meldung als letzten Fehler des aktuellen Threads.
+pub(crate) fn set_last_error(code: BundleErrorCode, message: impl Into<String>) {
+    let mut message = message.into().into_bytes();
+    message.retain(|&b| b != 0);
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(LastError { code, message }));
+}
+
+/// Hinterlegt einen Rust-Fehler mit passendem Code und seiner `Display`-Meldung.
+pub(crate) fn report<E>(err: &E)
+where
+    E: std::fmt::Display,
+    for<'a> &'a E: Into<BundleErrorCode>,
+{
+    set_last_error(err.into(), err.to_string());
+}
+
+/// Hinterlegt einen Fehler für ein ungültiges Argument.
+pub(crate) fn report_invalid_argument(message: &str) {
+    set_last_error(BundleErrorCode::InvalidArgument, message);
+}
+
+/// Code des letzten Fehlers im aktuellen Thread, oder `Ok`, falls noch keiner auftrat.
+#[unsafe(no_mangle)]
+pub extern "C" fn bundle_last_error_code() -> BundleErrorCode {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(BundleErrorCode::Ok, |e| e.code))
+}
+
+/// Meldung des letzten Fehlers im aktuellen Thread, oder null, falls noch keiner auftrat.
+/// Der Zeiger gehört der Bibliothek und bleibt bis zum nächsten Fehler oder
+/// [`bundle_clear_last_error`] im selben Thread gültig. Nicht freigeben.
+#[unsafe(no_mangle)]
+pub extern "C" fn bundle_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |e| e.message.as_ptr())
+    })
+}
+
+/// Setzt den Fehlerzustand des aktuellen Threads zurück.
+#[unsafe(no_mangle)]
+pub extern "C" fn bundle_clear_last_error() {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+}
@@ -0,0 +1,172 @@
+//! C-Schnittstelle der Bibliothek.
+//!
+//! Alle Funktionen sind mit `extern "C"` und unverändertem Symbolnamen exportiert,
+//! damit C- und C++-Launcher die `.so` direkt linken können. Der zugehörige Header
+//! liegt unter `include/bundle.h` und wird mit cbindgen aus diesem Modul erzeugt.
+//!
+//! Konventionen:
+//! - Pfade werden als nullterminierte C-Strings übergeben.
+//! - Zurückgegebene Strings gehören dem Aufrufer und müssen mit
+//!   [`bundle_string_free`] freigegeben werden.
+//! - Fehler werden über [`bundle_last_error_code`] und
+//!   [`bundle_last_error_message`] gemeldet.
+
+use std::ffi::{c_char, c_int, CStr, CString, OsStr};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+mod error;
+pub use error::*;
+
+mod bundle_info;
+pub use bundle_info::*;
+
+use crate::{
+    bundle_load_info_config as load_info_config,
+    find_bundle_root as find_root,
+    get_current_launched_bundle_path as current_bundle_path,
+    is_bundle_at_path as bundle_at_path,
+    is_launched_from_bundle as launched_from_bundle,
+    validate_bundle as validate,
+    BundleError,
+};
+
+/// Wandelt einen C-String in einen Pfad um. Bei einem Nullzeiger wird ein
+/// `InvalidArgument`-Fehler hinterlegt und `None` zurückgegeben.
+///
+/// # Safety
+/// `ptr` muss entweder null sein oder auf einen gültigen, nullterminierten String zeigen.
+pub(crate) unsafe fn path_from_c<'a>(ptr: *const c_char) -> Option<&'a Path> {
+    if ptr.is_null() {
+        report_invalid_argument("path must not be null");
+        return None;
+    }
+    let bytes = unsafe { CStr::from_ptr(ptr) }.to_bytes();
+    Some(Path::new(OsStr::from_bytes(bytes)))
+}
+
+/// Wandelt einen Pfad in einen vom Aufrufer freizugebenden C-String um.
+/// Enthält der Pfad ein Nullbyte, wird ein Nullzeiger zurückgegeben.
+pub(crate) fn path_into_c(path: &Path) -> *mut c_char {
+    string_into_c(path.as_os_str().as_bytes())
+}
+
+/// Kopiert Bytes in einen vom Aufrufer freizugebenden C-String.
+pub(crate) fn string_into_c(bytes: &[u8]) -> *mut c_char {
+    match CString::new(bytes) {
+        Ok(s) => s.into_raw(),
+        Err(_) => {
+            set_last_error(BundleErrorCode::InvalidFormat, "string contains an interior NUL byte");
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Prüft, ob `path` ein gültiges Bundle ist (App, Toolset, Service oder Framework).
+/// Ein Nullzeiger ergibt `false` und einen `InvalidArgument`-Fehler.
+///
+/// # Safety
+/// `path` muss null sein oder auf einen gültigen, nullterminierten String zeigen.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_is_bundle_at_path(path: *const c_char) -> bool {
+    match unsafe { path_from_c(path) } {
+        Some(path) => bundle_at_path(path),
+        None => false,
+    }
+}
+
+/// Sucht das äußerste Bundle oberhalb von `exe_path`.
+/// Gibt den Bundle-Pfad als neuen String zurück oder null, falls keins gefunden
+/// wurde (Fehlercode `NotFound`).
+/// Der Rückgabewert muss mit [`bundle_string_free`] freigegeben werden.
+///
+/// # Safety
+/// `exe_path` muss null sein oder auf einen gültigen, nullterminierten String zeigen.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_find_bundle_root(exe_path: *const c_char) -> *mut c_char {
+    let Some(exe_path) = (unsafe { path_from_c(exe_path) }) else {
+        return std::ptr::null_mut();
+    };
+    match find_root(exe_path) {
+        Some(root) => path_into_c(&root),
+        None => {
+            report(&BundleError::NotFound(format!("no bundle above '{}'", exe_path.display())));
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Gibt den Pfad des Bundles zurück, aus dem das laufende Programm gestartet wurde,
+/// oder null, falls es nicht aus einem Bundle stammt oder ein Fehler aufgetreten ist.
+/// Der Rückgabewert muss mit [`bundle_string_free`] freigegeben werden.
+#[unsafe(no_mangle)]
+pub extern "C" fn bundle_get_current_launched_bundle_path() -> *mut c_char {
+    match current_bundle_path() {
+        Ok(Some(path)) => path_into_c(path),
+        Ok(None) => {
+            report(&BundleError::NotFound("program was not launched from a bundle".into()));
+            std::ptr::null_mut()
+        }
+        Err(e) => {
+            report(e);
+            std::ptr::null_mut()
+        }
+    }
+}
+
+/// Prüft, ob das laufende Programm aus einem Bundle gestartet wurde.
+/// Gibt `1` (ja), `0` (nein) oder `-1` bei einem Fehler zurück.
+#[unsafe(no_mangle)]
+pub extern "C" fn bundle_is_launched_from_bundle() -> c_int {
+    match launched_from_bundle() {
+        Ok(true) => 1,
+        Ok(false) => 0,
+        Err(e) => {
+            report(&e);
+            -1
+        }
+    }
+}
+
+/// Lädt die Info.json des aktuellen Bundles in das prozessweite Singleton.
+/// Gibt `0` bei Erfolg und `-1` bei einem Fehler zurück (auch wenn bereits geladen).
+#[unsafe(no_mangle)]
+pub extern "C" fn bundle_load_info_config() -> c_int {
+    match load_info_config() {
+        Ok(_) => 0,
+        Err(e) => {
+            report(&e);
+            -1
+        }
+    }
+}
+
+/// Validiert das aktuelle Bundle. Setzt voraus, dass die Info.json bereits mit
+/// [`bundle_load_info_config`] geladen wurde.
+/// Gibt `1` (gültig), `0` (ungültig) oder `-1` bei einem Fehler zurück.
+#[unsafe(no_mangle)]
+pub extern "C" fn bundle_validate_bundle() -> c_int {
+    match validate() {
+        Ok(result) if result.is_valid => 1,
+        Ok(result) => {
+            set_last_error(BundleErrorCode::ValidationInvalidFormat, result.message);
+            0
+        }
+        Err(e) => {
+            report(&e);
+            -1
+        }
+    }
+}
+
+/// Gibt einen von dieser Bibliothek zurückgegebenen String frei. Null wird ignoriert.
+///
+/// # Safety
+/// `s` muss null sein oder von einer `bundle_*`-Funktion stammen und darf
+/// nicht bereits freigegeben worden sein.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn bundle_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
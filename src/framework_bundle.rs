@@ -0,0 +1,247 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::bundle_load_info_config::load_bundle_info_file;
+use crate::{
+    valid_bundle_structure, Bundle, BundleError, BundleInfoConfigFile, BundleKind,
+    BundleValidationResult,
+};
+
+/// Name des Verzeichnisses mit den installierten Versionen.
+pub const FRAMEWORK_VERSIONS_DIR: &str = "Versions";
+/// Name des Symlinks auf die aktuelle Version innerhalb von `Versions/`.
+pub const FRAMEWORK_CURRENT_VERSION: &str = "Current";
+
+/// Framework-Bundle (`.frameworkd`), optional mit mehreren Versionen nebeneinander.
+///
+/// Versionierter Aufbau:
+/// ```text
+/// Foo.frameworkd/
+///   Content -> Versions/Current/Content
+///   Versions/
+///     1/Content/{Config.json, Info.json, ...}
+///     2/Content/{Config.json, Info.json, ...}
+///     Current -> 2
+/// ```
+/// Ohne `Versions/` verhält sich das Framework wie jedes andere Bundle.
+#[derive(Debug)]
+pub struct FrameworkBundle {
+    bundle: Bundle,
+}
+
+/// Eine installierte Version eines Frameworks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameworkVersion {
+    /// Verzeichnisname unter `Versions/`.
+    pub name: String,
+    /// Pfad zum Versionsverzeichnis.
+    pub path: PathBuf,
+}
+
+impl FrameworkVersion {
+    /// Pfad zum `Content/`-Verzeichnis dieser Version.
+    pub fn content_path(&self) -> PathBuf {
+        self.path.join("Content")
+    }
+
+    /// Lädt die Info.json dieser Version.
+    pub fn info(&self) -> Result<BundleInfoConfigFile, BundleError> {
+        load_bundle_info_file(&self.path)
+    }
+}
+
+impl FrameworkBundle {
+    /// Öffnet das Framework-Bundle unter `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<FrameworkBundle, BundleError> {
+        FrameworkBundle::from_bundle(Bundle::open(path)?)
+    }
+
+    /// Wandelt ein geöffnetes Bundle um. Schlägt fehl, wenn es kein Framework ist.
+    pub fn from_bundle(bundle: Bundle) -> Result<FrameworkBundle, BundleError> {
+        if bundle.kind() != BundleKind::Framework {
+            return Err(BundleError::InvalidFormat(format!(
+                "'{}' is not a framework bundle",
+                bundle.path().display()
+            )));
+        }
+        Ok(FrameworkBundle { bundle })
+    }
+
+    /// Das zugrunde liegende Bundle.
+    pub fn bundle(&self) -> &Bundle {
+        &self.bundle
+    }
+
+    fn versions_path(&self) -> PathBuf {
+        self.bundle.path().join(FRAMEWORK_VERSIONS_DIR)
+    }
+
+    /// Gibt an, ob das Framework den `Versions/`-Aufbau verwendet.
+    pub fn is_versioned(&self) -> bool {
+        self.versions_path().is_dir()
+    }
+
+    /// Alle installierten Versionen, nach Namen sortiert. Symlinks (wie `Current`)
+    /// werden nicht aufgeführt. Nicht versionierte Frameworks liefern eine leere Liste.
+    pub fn versions(&self) -> Result<Vec<FrameworkVersion>, BundleError> {
+        if !self.is_versioned() {
+            return Ok(Vec::new());
+        }
+        let mut versions = Vec::new();
+        for entry in fs::read_dir(self.versions_path())? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            if let Some(name) = entry.file_name().to_str() {
+                versions.push(FrameworkVersion { name: name.to_string(), path: entry.path() });
+            }
+        }
+        versions.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(versions)
+    }
+
+    /// Die Version, auf die `Versions/Current` zeigt, oder `None` bei nicht
+    /// versionierten Frameworks. Ein ungültiger `Current`-Link ergibt `InvalidFormat`.
+    pub fn current_version(&self) -> Result<Option<FrameworkVersion>, BundleError> {
+        if !self.is_versioned() {
+            return Ok(None);
+        }
+        let link = self.versions_path().join(FRAMEWORK_CURRENT_VERSION);
+        let name = current_link_target(&link)?;
+        let path = self.versions_path().join(&name);
+        if !path.is_dir() {
+            return Err(BundleError::NotFound(format!(
+                "current version '{}' does not exist in '{}'",
+                name,
+                self.versions_path().display()
+            )));
+        }
+        Ok(Some(FrameworkVersion { name, path }))
+    }
+
+    /// Prüft den Versionsaufbau:
+    /// - `Versions/Current` ist ein relativer Symlink auf ein Versionsverzeichnis,
+    /// - jede Version hat eine gültige Bundle-Struktur,
+    /// - `Content` zeigt auf den Inhalt der aktuellen Version,
+    /// - kein Symlink im Framework zeigt aus dem Bundle heraus.
+    pub fn validate_versions(&self) -> BundleValidationResult {
+        let mut problems = Vec::new();
+        let mut warnings = Vec::new();
+
+        if self.is_versioned() {
+            self.check_versions(&mut problems, &mut warnings);
+        }
+        self.check_symlinks_stay_inside(&mut problems);
+
+        BundleValidationResult {
+            is_valid: problems.is_empty(),
+            message: if problems.is_empty() {
+                "Framework-Versionen sind gültig".to_string()
+            } else {
+                problems.join("; ")
+            },
+            warnings,
+        }
+    }
+
+    fn check_versions(&self, problems: &mut Vec<String>, warnings: &mut Vec<String>) {
+        let versions = match self.versions() {
+            Ok(versions) => versions,
+            Err(e) => {
+                problems.push(format!("cannot read versions: {}", e));
+                return;
+            }
+        };
+        if versions.is_empty() {
+            problems.push("Versions/ contains no version".to_string());
+        }
+        for version in &versions {
+            if !valid_bundle_structure(&version.path) {
+                problems.push(format!("version '{}' has no Content/Config.json", version.name));
+            }
+        }
+
+        let current = match self.current_version() {
+            Ok(Some(current)) => current,
+            Ok(None) => return,
+            Err(e) => {
+                problems.push(e.to_string());
+                return;
+            }
+        };
+
+        let content = self.bundle.path().join("Content");
+        match fs::symlink_metadata(&content) {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                let resolved = content.canonicalize().ok();
+                let expected = current.content_path().canonicalize().ok();
+                if resolved.is_none() || resolved != expected {
+                    problems.push(format!(
+                        "Content does not point to the current version '{}'",
+                        current.name
+                    ));
+                }
+            }
+            Ok(_) => warnings.push(
+                "Content is not a symlink to Versions/Current/Content".to_string(),
+            ),
+            Err(_) => problems.push("Content is missing".to_string()),
+        }
+    }
+
+    fn check_symlinks_stay_inside(&self, problems: &mut Vec<String>) {
+        let root = self.bundle.path();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    pending.push(path);
+                } else if file_type.is_symlink() {
+                    match path.canonicalize() {
+                        Ok(target) if target.starts_with(root) => {}
+                        Ok(target) => problems.push(format!(
+                            "symlink '{}' points outside the bundle to '{}'",
+                            relative(root, &path).display(),
+                            target.display()
+                        )),
+                        Err(_) => problems.push(format!(
+                            "symlink '{}' is dangling",
+                            relative(root, &path).display()
+                        )),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Liest den `Current`-Link. Er muss relativ sein und genau ein Verzeichnis benennen.
+fn current_link_target(link: &Path) -> Result<String, BundleError> {
+    let target = fs::read_link(link).map_err(|e| {
+        BundleError::InvalidFormat(format!("'{}' is not a symlink: {}", link.display(), e))
+    })?;
+    let mut components = target.components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(name)), None) => name
+            .to_str()
+            .map(str::to_string)
+            .ok_or_else(|| BundleError::InvalidFormat("version name is not valid UTF-8".into())),
+        _ => Err(BundleError::InvalidFormat(format!(
+            "'{}' must point to a sibling version directory, not '{}'",
+            link.display(),
+            target.display()
+        ))),
+    }
+}
+
+fn relative<'a>(root: &Path, path: &'a Path) -> &'a Path {
+    path.strip_prefix(root).unwrap_or(path)
+}
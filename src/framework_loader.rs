@@ -0,0 +1,134 @@
+use std::ffi::{c_void, CStr, CString};
+use std::marker::PhantomData;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use crate::{detect_elf_architecture, Architecture, BundleError, FrameworkBundle};
+
+/// Per `dlopen` geladenes Framework. Wird beim Drop mit `dlclose` entladen.
+#[derive(Debug)]
+pub struct LoadedFramework {
+    handle: *mut c_void,
+    path: PathBuf,
+}
+
+// dlopen-Handles und dlsym sind laut POSIX threadsicher.
+unsafe impl Send for LoadedFramework {}
+unsafe impl Sync for LoadedFramework {}
+
+/// Typisiertes Symbol aus einem [`LoadedFramework`]; kann das Framework nicht überleben.
+#[derive(Debug)]
+pub struct Symbol<'lib, T> {
+    value: T,
+    _lib: PhantomData<&'lib LoadedFramework>,
+}
+
+impl<T> std::ops::Deref for Symbol<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Liefert die letzte `dlerror`-Meldung des aktuellen Threads.
+fn last_dl_error() -> String {
+    let message = unsafe { libc::dlerror() };
+    if message.is_null() {
+        "unknown dynamic loader error".to_string()
+    } else {
+        unsafe { CStr::from_ptr(message) }.to_string_lossy().into_owned()
+    }
+}
+
+impl FrameworkBundle {
+    /// Pfad zum Binary des Frameworks (`entry_point` relativ zu `Content/`).
+    /// Bei versionierten Frameworks ist das die aktuelle Version.
+    pub fn executable_path(&self) -> Result<PathBuf, BundleError> {
+        self.bundle().executable_path()
+    }
+
+    /// Lädt das Framework-Binary per `dlopen`.
+    ///
+    /// Das Binary muss existieren und dieselbe ELF-Architektur wie der Host haben;
+    /// sonst wird `NotFound` bzw. `InvalidFormat` zurückgegeben.
+    pub fn load(&self) -> Result<LoadedFramework, BundleError> {
+        let path = self.executable_path()?;
+        if !path.is_file() {
+            return Err(BundleError::NotFound(format!(
+                "framework binary not found at '{}'",
+                path.display()
+            )));
+        }
+
+        let arch = detect_elf_architecture(&path)
+            .map_err(|e| BundleError::InvalidFormat(format!("'{}': {}", path.display(), e)))?;
+        if arch != Architecture::host() {
+            return Err(BundleError::InvalidFormat(format!(
+                "'{}' is built for {:?}, host is {:?}",
+                path.display(),
+                arch,
+                Architecture::host()
+            )));
+        }
+
+        LoadedFramework::open(&path)
+    }
+}
+
+impl LoadedFramework {
+    fn open(path: &Path) -> Result<LoadedFramework, BundleError> {
+        let c_path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|_| BundleError::InvalidFormat("path contains a NUL byte".into()))?;
+        let handle = unsafe { libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL) };
+        if handle.is_null() {
+            return Err(BundleError::InvalidFormat(format!(
+                "dlopen '{}' failed: {}",
+                path.display(),
+                last_dl_error()
+            )));
+        }
+        Ok(LoadedFramework { handle, path: path.to_path_buf() })
+    }
+
+    /// Pfad des geladenen Binaries.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Sucht das Symbol `name` und gibt es als `T` zurück.
+    ///
+    /// # Safety
+    /// `T` muss dem tatsächlichen Typ des Symbols entsprechen, typischerweise ein
+    /// `extern "C" fn`-Zeiger oder ein Rohzeiger. `T` muss zeigergroß sein.
+    pub unsafe fn symbol<T: Copy>(&self, name: &str) -> Result<Symbol<'_, T>, BundleError> {
+        if std::mem::size_of::<T>() != std::mem::size_of::<*mut c_void>() {
+            return Err(BundleError::InvalidFormat(format!(
+                "symbol type for '{}' must be pointer-sized",
+                name
+            )));
+        }
+        let c_name = CString::new(name)
+            .map_err(|_| BundleError::InvalidFormat("symbol name contains a NUL byte".into()))?;
+
+        // dlerror zurücksetzen, da ein Symbol legitim den Wert null haben kann
+        unsafe { libc::dlerror() };
+        let address = unsafe { libc::dlsym(self.handle, c_name.as_ptr()) };
+        if address.is_null() && !unsafe { libc::dlerror() }.is_null() {
+            return Err(BundleError::NotFound(format!(
+                "symbol '{}' not found in '{}'",
+                name,
+                self.path.display()
+            )));
+        }
+
+        let value = unsafe { std::mem::transmute_copy::<*mut c_void, T>(&address) };
+        Ok(Symbol { value, _lib: PhantomData })
+    }
+}
+
+impl Drop for LoadedFramework {
+    fn drop(&mut self) {
+        unsafe { libc::dlclose(self.handle) };
+    }
+}
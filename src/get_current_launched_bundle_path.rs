@@ -12,6 +12,7 @@ static BUNDLE_PATH: OnceCell<Result<Option<PathBuf>, BundleError>> = OnceCell::n
 /// Gibt bei Erfolg einen Option<PathBuf> zurück:
 /// - Some(pfad) falls ein Bundle gefunden wurde,
 /// - None falls kein Bundle gefunden wurde.
+///
 /// Gibt bei Fehlern ein passendes BundleError zurück.
 pub fn get_current_launched_bundle_path() -> &'static Result<Option<PathBuf>, BundleError> {
     BUNDLE_PATH.get_or_init(|| {
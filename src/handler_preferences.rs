@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::atomic_file::write_atomically;
+use crate::{xdg, BundleError};
+
+/// Vom Benutzer gewählte Standard-Handler, jeweils als Bundle-Bezeichner.
+///
+/// Gespeichert als JSON unter [`HandlerPreferences::default_path`]:
+/// ```json
+/// {
+///   "url_schemes": { "https": "org.example.browser" },
+///   "extensions": { "tar.gz": "org.example.archiver" }
+/// }
+/// ```
+/// Schlüssel werden kleingeschrieben abgelegt und verglichen.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HandlerPreferences {
+    /// URL-Scheme → Bezeichner des Standard-Handlers.
+    pub url_schemes: HashMap<String, String>,
+    /// Dateiendung (ohne führenden Punkt) → Bezeichner des Standard-Handlers.
+    pub extensions: HashMap<String, String>,
+}
+
+impl HandlerPreferences {
+    /// `$XDG_CONFIG_HOME/bundle/handlers.json` bzw. `~/.config/bundle/handlers.json`.
+    pub fn default_path() -> Option<PathBuf> {
+        xdg::config_home().map(|config| config.join("bundle/handlers.json"))
+    }
+
+    /// Lädt die Einstellungen aus `path`. Eine fehlende Datei ergibt leere Einstellungen.
+    /// Von Hand eingetragene Schlüssel werden wie bei den Settern normalisiert.
+    pub fn load(path: &Path) -> Result<HandlerPreferences, BundleError> {
+        if !path.exists() {
+            return Ok(HandlerPreferences::default());
+        }
+        let data = std::fs::read(path)?;
+        let preferences: HandlerPreferences = serde_json::from_slice(&data).map_err(|e| {
+            BundleError::InvalidFormat(format!("Failed to parse '{}': {}", path.display(), e))
+        })?;
+        Ok(preferences.normalized())
+    }
+
+    fn normalized(self) -> HandlerPreferences {
+        let mut normalized = HandlerPreferences::default();
+        for (scheme, identifier) in &self.url_schemes {
+            normalized.set_url_handler(scheme, identifier);
+        }
+        for (extension, identifier) in &self.extensions {
+            normalized.set_extension_handler(extension, identifier);
+        }
+        normalized
+    }
+
+    /// Lädt die Einstellungen vom Standardpfad, sofern dieser bestimmbar ist.
+    pub fn load_default() -> Result<HandlerPreferences, BundleError> {
+        match HandlerPreferences::default_path() {
+            Some(path) => HandlerPreferences::load(&path),
+            None => Ok(HandlerPreferences::default()),
+        }
+    }
+
+    /// Schreibt die Einstellungen atomar nach `path` (temporäre Datei im selben
+    /// Verzeichnis, dann `rename`) und legt fehlende Verzeichnisse an.
+    pub fn save(&self, path: &Path) -> Result<(), BundleError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let data = serde_json::to_vec_pretty(self)
+            .map_err(|e| BundleError::InvalidFormat(format!("Failed to serialize handler preferences: {}", e)))?;
+        Ok(write_atomically(path, &data, None)?)
+    }
+
+    /// Standard-Handler für `scheme`.
+    pub fn url_handler(&self, scheme: &str) -> Option<&str> {
+        self.url_schemes.get(&scheme.to_ascii_lowercase()).map(String::as_str)
+    }
+
+    /// Setzt den Standard-Handler für `scheme`.
+    pub fn set_url_handler(&mut self, scheme: &str, identifier: &str) {
+        self.url_schemes.insert(scheme.to_ascii_lowercase(), identifier.to_string());
+    }
+
+    /// Standard-Handler für die Dateiendung `extension`.
+    pub fn extension_handler(&self, extension: &str) -> Option<&str> {
+        self.extensions.get(&normalize_extension(extension)).map(String::as_str)
+    }
+
+    /// Setzt den Standard-Handler für die Dateiendung `extension`.
+    pub fn set_extension_handler(&mut self, extension: &str, identifier: &str) {
+        self.extensions.insert(normalize_extension(extension), identifier.to_string());
+    }
+}
+
+/// Entfernt einen führenden Punkt und schreibt die Endung klein (`.TAR.GZ` → `tar.gz`).
+pub(crate) fn normalize_extension(extension: &str) -> String {
+    extension.trim_start_matches('.').to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_normalizes_hand_edited_keys() {
+        let dir = std::env::temp_dir().join(format!("bundle-handlers-{}", std::process::id()));
+        let path = dir.join("handlers.json");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            &path,
+            r#"{ "url_schemes": { "HTTPS": "org.example.browser" }, "extensions": { ".TAR.GZ": "org.example.archiver" } }"#,
+        )
+        .unwrap();
+
+        let preferences = HandlerPreferences::load(&path).unwrap();
+        assert_eq!(preferences.url_handler("https"), Some("org.example.browser"));
+        assert_eq!(preferences.extension_handler("tar.gz"), Some("org.example.archiver"));
+        assert!(preferences.url_schemes.contains_key("https"));
+        assert!(preferences.extensions.contains_key("tar.gz"));
+
+        preferences.save(&path).unwrap();
+        let reloaded = HandlerPreferences::load(&path).unwrap();
+        assert_eq!(reloaded.url_schemes, preferences.url_schemes);
+        assert_eq!(reloaded.extensions, preferences.extensions);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -8,7 +8,7 @@ use crate::valid_bundle_structure; // Prüft, ob die Bundle-Verzeichnisstruktur
 /// 3. Die Bundle-Struktur ist gültig (Content/ und Content/Config.json existieren).
 pub fn is_app_bundle_dir(path: &Path) -> bool {
     path.is_dir() 
-        && path.extension().map_or(false, |ext| ext == "appd")
+        && path.extension().is_some_and(|ext| ext == "appd")
         && valid_bundle_structure(path)
 }
 
@@ -19,7 +19,7 @@ pub fn is_app_bundle_dir(path: &Path) -> bool {
 /// 3. Die Bundle-Struktur ist gültig.
 pub fn is_service_bundle_dir(path: &Path) -> bool {
     path.is_dir() 
-        && path.extension().map_or(false, |ext| ext == "serviced")
+        && path.extension().is_some_and(|ext| ext == "serviced")
         && valid_bundle_structure(path)
 }
 
@@ -30,7 +30,7 @@ pub fn is_service_bundle_dir(path: &Path) -> bool {
 /// 3. Die Bundle-Struktur ist gültig.
 pub fn is_toolset_bundle_dir(path: &Path) -> bool {
     path.is_dir() 
-        && path.extension().map_or(false, |ext| ext == "toolsetd")
+        && path.extension().is_some_and(|ext| ext == "toolsetd")
         && valid_bundle_structure(path)
 }
 
@@ -41,6 +41,6 @@ pub fn is_toolset_bundle_dir(path: &Path) -> bool {
 /// 3. Die Bundle-Struktur ist gültig.
 pub fn is_framework_bundle_dir(path: &Path) -> bool {
     path.is_dir() 
-        && path.extension().map_or(false, |ext| ext == "frameworkd")
+        && path.extension().is_some_and(|ext| ext == "frameworkd")
         && valid_bundle_structure(path)
 }
@@ -0,0 +1,466 @@
+use std::ffi::{CString, OsStr, OsString};
+use std::io::Read;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+
+use crate::executables::stays_inside;
+use crate::launch_config::expand;
+use crate::socket_activation::{ENV_LISTEN_FDNAMES, ENV_LISTEN_FDS, ENV_LISTEN_PID, SD_LISTEN_FDS_START};
+use crate::{detect_elf_architecture, xdg, Architecture, Bundle, BundleError};
+
+/// Umgebungsvariablen, die jedem gestarteten Bundle übergeben werden.
+pub const ENV_BUNDLE_IDENTIFIER: &str = "BUNDLE_IDENTIFIER";
+pub const ENV_BUNDLE_PATH: &str = "BUNDLE_PATH";
+pub const ENV_BUNDLE_CONTENT_PATH: &str = "BUNDLE_CONTENT_PATH";
+pub const ENV_BUNDLE_EXECUTABLE: &str = "BUNDLE_EXECUTABLE";
+pub const ENV_BUNDLE_DATA_DIR: &str = "BUNDLE_DATA_DIR";
+pub const ENV_BUNDLE_CACHE_DIR: &str = "BUNDLE_CACHE_DIR";
+pub const ENV_BUNDLE_CONFIG_DIR: &str = "BUNDLE_CONFIG_DIR";
+
+/// Benutzerbezogene Verzeichnisse eines Bundles, jeweils ein Unterverzeichnis
+/// mit dem Bezeichner unter den XDG-Basisverzeichnissen.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BundleDataDirectories {
+    /// `$XDG_DATA_HOME/<identifier>`
+    pub data: Option<PathBuf>,
+    /// `$XDG_CACHE_HOME/<identifier>`
+    pub cache: Option<PathBuf>,
+    /// `$XDG_CONFIG_HOME/<identifier>`
+    pub config: Option<PathBuf>,
+}
+
+/// Ein per [`Bundle::launch`] gestarteter Kindprozess.
+/// Wird der Handle verworfen, läuft der Prozess weiter und wird nicht eingesammelt.
+#[derive(Debug)]
+pub struct BundleProcess {
+    pid: libc::pid_t,
+    status: Option<ExitStatus>,
+}
+
+impl BundleProcess {
+    /// Prozess-ID des Kindprozesses.
+    pub fn pid(&self) -> u32 {
+        self.pid as u32
+    }
+
+    fn wait_with(&mut self, flags: libc::c_int) -> Result<Option<ExitStatus>, BundleError> {
+        if let Some(status) = self.status {
+            return Ok(Some(status));
+        }
+        let mut raw = 0;
+        loop {
+            let pid = unsafe { libc::waitpid(self.pid, &mut raw, flags) };
+            if pid == 0 {
+                return Ok(None);
+            }
+            if pid < 0 {
+                let err = std::io::Error::last_os_error();
+                if err.kind() == std::io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return Err(BundleError::IoError(err));
+            }
+            let status = ExitStatus::from_raw(raw);
+            self.status = Some(status);
+            return Ok(Some(status));
+        }
+    }
+
+    /// Wartet auf das Ende des Prozesses.
+    pub fn wait(&mut self) -> Result<ExitStatus, BundleError> {
+        self.wait_with(0).map(|status| status.expect("waitpid without WNOHANG returned no status"))
+    }
+
+    /// Liefert den Exit-Status, falls der Prozess bereits beendet ist.
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>, BundleError> {
+        self.wait_with(libc::WNOHANG)
+    }
+
+    /// Sendet `signal` an den Prozess.
+    pub fn kill(&self, signal: libc::c_int) -> Result<(), BundleError> {
+        if self.status.is_some() {
+            return Ok(());
+        }
+        if unsafe { libc::kill(self.pid, signal) } < 0 {
+            return Err(BundleError::IoError(std::io::Error::last_os_error()));
+        }
+        Ok(())
+    }
+}
+
+/// Prüft, ob `path` eine ausführbare Datei für diesen Host ist:
+/// ein ELF-Binary der Host-Architektur oder ein Skript mit `#!`-Zeile.
+pub(crate) fn check_executable(path: &Path) -> Result<(), BundleError> {
+    if !path.is_file() {
+        return Err(BundleError::NotFound(format!("executable not found at '{}'", path.display())));
+    }
+    let c_path = cstring(path.as_os_str())?;
+    if unsafe { libc::access(c_path.as_ptr(), libc::X_OK) } != 0 {
+        return Err(BundleError::InvalidFormat(format!("'{}' is not executable", path.display())));
+    }
+
+    let mut magic = [0u8; 4];
+    let read = std::fs::File::open(path)?.read(&mut magic)?;
+    if read >= 2 && magic.starts_with(b"#!") {
+        return Ok(());
+    }
+    let arch = detect_elf_architecture(path)
+        .map_err(|e| BundleError::InvalidFormat(format!("'{}' is neither ELF nor a script: {}", path.display(), e)))?;
+    if arch != Architecture::host() {
+        return Err(BundleError::InvalidFormat(format!(
+            "'{}' is built for {:?}, host is {:?}",
+            path.display(),
+            arch,
+            Architecture::host()
+        )));
+    }
+    Ok(())
+}
+
+/// Bezeichner werden als Verzeichnisname unter den XDG-Verzeichnissen verwendet;
+/// erlaubt sind wie bei systemd-Unit-Namen nur ASCII-Buchstaben, Ziffern und `:_.-`.
+fn check_directory_identifier(identifier: &str) -> Result<(), BundleError> {
+    if identifier.is_empty()
+        || identifier == "."
+        || identifier == ".."
+        || !identifier.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, ':' | '_' | '.' | '-'))
+    {
+        return Err(BundleError::InvalidFormat(format!(
+            "identifier '{}' cannot be used as a directory name",
+            identifier
+        )));
+    }
+    Ok(())
+}
+
+fn cstring(s: &OsStr) -> Result<CString, BundleError> {
+    CString::new(s.as_bytes())
+        .map_err(|_| BundleError::InvalidFormat(format!("'{}' contains a NUL byte", s.to_string_lossy())))
+}
+
+/// Dupliziert `fd` auf die kleinste freie Nummer ab `min` (mit `FD_CLOEXEC`).
+fn park_fd(fd: RawFd, min: RawFd) -> Result<OwnedFd, BundleError> {
+    let parked = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, min) };
+    if parked < 0 {
+        return Err(BundleError::IoError(std::io::Error::last_os_error()));
+    }
+    Ok(unsafe { OwnedFd::from_raw_fd(parked) })
+}
+
+/// Schreibt `value` dezimal mit abschließendem NUL nach `out`; async-signal-sicher.
+///
+/// # Safety
+/// `out` muss Platz für 21 Bytes haben.
+unsafe fn write_decimal(out: *mut u8, mut value: u64) {
+    let mut digits = [0u8; 20];
+    let mut len = 0;
+    loop {
+        digits[len] = b'0' + (value % 10) as u8;
+        len += 1;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    for index in 0..len {
+        unsafe { *out.add(index) = digits[len - 1 - index] };
+    }
+    unsafe { *out.add(len) = 0 };
+}
+
+/// Ein aufgelöster Programmaufruf: Programm, Argumente, Bundle-spezifische
+/// Umgebung und Arbeitsverzeichnis. Siehe [`Bundle::launch_command`].
+#[derive(Debug, Clone)]
+pub struct LaunchCommand {
+    pub(crate) program: PathBuf,
+    pub(crate) args: Vec<OsString>,
+    pub(crate) env: Vec<(OsString, OsString)>,
+    pub(crate) working_directory: PathBuf,
+}
+
+impl LaunchCommand {
+    pub fn program(&self) -> &Path {
+        &self.program
+    }
+
+    pub fn arguments(&self) -> &[OsString] {
+        &self.args
+    }
+
+    /// Variablen, die zusätzlich zur geerbten Umgebung gesetzt werden.
+    pub fn environment(&self) -> &[(OsString, OsString)] {
+        &self.env
+    }
+
+    pub fn working_directory(&self) -> &Path {
+        &self.working_directory
+    }
+
+    /// Setzt eine Variable; spätere Werte überschreiben frühere.
+    pub(crate) fn set_env(&mut self, key: impl Into<OsString>, value: impl Into<OsString>) {
+        let key = key.into();
+        self.env.retain(|(k, _)| *k != key);
+        self.env.push((key, value.into()));
+    }
+
+    /// Startet den Prozess per `fork`/`execve` mit der geerbten Umgebung
+    /// ergänzt um [`environment`](Self::environment).
+    pub fn spawn(&self) -> Result<BundleProcess, BundleError> {
+        self.spawn_with_sockets(&[])
+    }
+
+    /// Wie [`spawn`](Self::spawn), übergibt aber `sockets` nach dem
+    /// LISTEN_FDS-Protokoll ab Deskriptor 3, in dieser Reihenfolge.
+    pub(crate) fn spawn_with_sockets(&self, sockets: &[(String, OwnedFd)]) -> Result<BundleProcess, BundleError> {
+        // Alles vor dem fork vorbereiten: im Kind sind nur async-signal-sichere Aufrufe erlaubt
+        let program = cstring(self.program.as_os_str())?;
+        let cwd = cstring(self.working_directory.as_os_str())?;
+        let argv: Vec<CString> = std::iter::once(self.program.as_os_str())
+            .chain(self.args.iter().map(OsString::as_os_str))
+            .map(cstring)
+            .collect::<Result<_, _>>()?;
+        // Geerbte LISTEN_*-Variablen gelten nur für diesen Prozess, nie für das Kind
+        let mut env: Vec<(OsString, OsString)> = std::env::vars_os()
+            .filter(|(key, _)| !self.env.iter().any(|(k, _)| k == key))
+            .filter(|(key, _)| ![ENV_LISTEN_FDS, ENV_LISTEN_PID, ENV_LISTEN_FDNAMES].iter().any(|k| key == k))
+            .collect();
+        env.extend(self.env.iter().cloned());
+        if !sockets.is_empty() {
+            env.push((ENV_LISTEN_FDS.into(), sockets.len().to_string().into()));
+            let names: Vec<&str> = sockets.iter().map(|(name, _)| name.as_str()).collect();
+            env.push((ENV_LISTEN_FDNAMES.into(), names.join(":").into()));
+        }
+        let envp: Vec<CString> = env
+            .iter()
+            .map(|(k, v)| {
+                let mut entry = k.clone();
+                entry.push("=");
+                entry.push(v);
+                cstring(&entry)
+            })
+            .collect::<Result<_, _>>()?;
+        let mut argv_ptrs: Vec<*const libc::c_char> = argv.iter().map(|a| a.as_ptr()).collect();
+        argv_ptrs.push(std::ptr::null());
+        let mut envp_ptrs: Vec<*const libc::c_char> = envp.iter().map(|e| e.as_ptr()).collect();
+        // LISTEN_PID kennt erst das Kind; Platz für die Ziffern wird hier reserviert
+        let mut listen_pid = format!("{}=", ENV_LISTEN_PID).into_bytes();
+        let listen_pid_digits = listen_pid.len();
+        listen_pid.resize(listen_pid_digits + 21, 0);
+        if !sockets.is_empty() {
+            envp_ptrs.push(listen_pid.as_ptr().cast());
+        }
+        envp_ptrs.push(std::ptr::null());
+
+        // Sockets oberhalb der Zielnummern parken, damit dup2 im Kind nichts überschreibt
+        let first_free = SD_LISTEN_FDS_START + sockets.len() as RawFd;
+        let parked: Vec<OwnedFd> = sockets
+            .iter()
+            .map(|(_, fd)| park_fd(fd.as_raw_fd(), first_free))
+            .collect::<Result<_, _>>()?;
+        let parked_raw: Vec<RawFd> = parked.iter().map(|fd| fd.as_raw_fd()).collect();
+
+        // Über diese Pipe meldet das Kind einen fehlgeschlagenen exec (errno)
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+            return Err(BundleError::IoError(std::io::Error::last_os_error()));
+        }
+        let (read_end, pipe_write) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+        let write_end = park_fd(pipe_write.as_raw_fd(), first_free)?;
+        drop(pipe_write);
+        let write_raw = write_end.as_raw_fd();
+        let listen_pid_ptr = listen_pid.as_mut_ptr();
+        let mut empty_mask: libc::sigset_t = unsafe { std::mem::zeroed() };
+        unsafe { libc::sigemptyset(&mut empty_mask) };
+
+        let pid = unsafe { libc::fork() };
+        if pid < 0 {
+            return Err(BundleError::IoError(std::io::Error::last_os_error()));
+        }
+        if pid == 0 {
+            unsafe {
+                // Signalmaske und ignoriertes SIGPIPE (Rust-Laufzeit) überdauern execve
+                libc::sigprocmask(libc::SIG_SETMASK, &empty_mask, std::ptr::null_mut());
+                libc::signal(libc::SIGPIPE, libc::SIG_DFL);
+                let mut ok = true;
+                for (index, &fd) in parked_raw.iter().enumerate() {
+                    // dup2 löscht FD_CLOEXEC auf dem Ziel
+                    ok &= libc::dup2(fd, SD_LISTEN_FDS_START + index as RawFd) >= 0;
+                }
+                if ok && !parked_raw.is_empty() {
+                    write_decimal(listen_pid_ptr.add(listen_pid_digits), libc::getpid() as u64);
+                }
+                if ok && libc::chdir(cwd.as_ptr()) == 0 {
+                    libc::execve(program.as_ptr(), argv_ptrs.as_ptr(), envp_ptrs.as_ptr());
+                }
+                let errno = *libc::__errno_location();
+                let bytes = errno.to_ne_bytes();
+                libc::write(write_raw, bytes.as_ptr().cast(), bytes.len());
+                libc::_exit(127);
+            }
+        }
+
+        drop(write_end);
+        drop(parked);
+        let mut process = BundleProcess { pid, status: None };
+        let mut errno = [0u8; 4];
+        let mut pipe = std::fs::File::from(read_end);
+        match pipe.read_exact(&mut errno) {
+            // EOF: exec war erfolgreich und hat die Pipe geschlossen
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => Ok(process),
+            Err(e) => Err(BundleError::IoError(e)),
+            Ok(()) => {
+                let _ = process.wait();
+                Err(BundleError::IoError(std::io::Error::from_raw_os_error(i32::from_ne_bytes(errno))))
+            }
+        }
+    }
+}
+
+impl Bundle {
+    /// Pfad zum Haupt-Executable (`entry_point` relativ zu `Content/`).
+    /// Ein `entry_point`, der `Content/` verlässt, ergibt `InvalidFormat`.
+    pub fn executable_path(&self) -> Result<PathBuf, BundleError> {
+        let entry_point = &self.info()?.entry_point;
+        if !stays_inside(Path::new(entry_point)) {
+            return Err(BundleError::InvalidFormat(format!(
+                "entry_point points outside Content/: '{}'",
+                entry_point
+            )));
+        }
+        Ok(self.content_path().join(entry_point))
+    }
+
+    /// Benutzerbezogene Daten-, Cache- und Konfigurationsverzeichnisse des Bundles.
+    /// Schlägt fehl, wenn der Bezeichner kein einzelner, unverdächtiger Pfadbestandteil ist.
+    pub fn data_directories(&self) -> Result<BundleDataDirectories, BundleError> {
+        let identifier = self.identifier()?;
+        check_directory_identifier(identifier)?;
+        Ok(BundleDataDirectories {
+            data: xdg::data_home().map(|dir| dir.join(identifier)),
+            cache: xdg::cache_home().map(|dir| dir.join(identifier)),
+            config: xdg::config_home().map(|dir| dir.join(identifier)),
+        })
+    }
+
+    /// Standard-Aufruf für `program`: Bundle-Variablen, Arbeitsverzeichnis `Content/`.
+    pub(crate) fn command_for(&self, program: PathBuf) -> Result<LaunchCommand, BundleError> {
+        let mut command = LaunchCommand {
+            program,
+            args: Vec::new(),
+            env: Vec::new(),
+            working_directory: self.content_path(),
+        };
+        command.set_env(ENV_BUNDLE_IDENTIFIER, self.identifier()?);
+        command.set_env(ENV_BUNDLE_PATH, self.path());
+        command.set_env(ENV_BUNDLE_CONTENT_PATH, self.content_path());
+        command.set_env(ENV_BUNDLE_EXECUTABLE, command.program.clone());
+
+        let dirs = self.data_directories()?;
+        for (key, dir) in [
+            (ENV_BUNDLE_DATA_DIR, dirs.data),
+            (ENV_BUNDLE_CACHE_DIR, dirs.cache),
+            (ENV_BUNDLE_CONFIG_DIR, dirs.config),
+        ] {
+            if let Some(dir) = dir {
+                command.set_env(key, dir);
+            }
+        }
+        Ok(command)
+    }
+
+    /// Löst den Aufruf des Haupt-Executables auf, ohne ihn zu starten.
+    ///
+    /// Argumente aus `launch.arguments` stehen vor `args`. Variablen aus
+    /// `launch.environment` überschreiben die `BUNDLE_*`-Variablen, Einträge
+    /// aus `env` wiederum beide. Platzhalter werden mit den Werten der
+    /// `BUNDLE_*`-Variablen ersetzt.
+    pub fn launch_command(&self, args: &[&str], env: &[(&str, &str)]) -> Result<LaunchCommand, BundleError> {
+        let launch = &self.info()?.launch;
+        let mut command = self.command_for(self.executable_path()?)?;
+        let bundle_env = command.env.clone();
+
+        for argument in &launch.arguments {
+            command.args.push(expand(argument, &bundle_env)?);
+        }
+        command.args.extend(args.iter().map(OsString::from));
+
+        let mut declared: Vec<_> = launch.environment.iter().collect();
+        declared.sort();
+        for (key, value) in declared {
+            command.set_env(key, expand(value, &bundle_env)?);
+        }
+        for (key, value) in env {
+            command.set_env(key, value);
+        }
+        command.working_directory = launch.resolve_working_directory(&self.content_path(), &bundle_env)?;
+        Ok(command)
+    }
+
+    /// Startet das Haupt-Executable des Bundles.
+    ///
+    /// Das Executable muss existieren, ausführbar sein und zur Host-Architektur
+    /// passen (Skripte mit `#!` sind erlaubt). Der Aufruf wird wie bei
+    /// [`launch_command`](Self::launch_command) aufgelöst; der Prozess erbt die
+    /// aktuelle Umgebung. Die Datenverzeichnisse werden bei Bedarf angelegt.
+    /// Deklarierte `service.sockets` werden angelegt und per LISTEN_FDS übergeben.
+    pub fn launch(&self, args: &[&str], env: &[(&str, &str)]) -> Result<BundleProcess, BundleError> {
+        let command = self.launch_command(args, env)?;
+        check_executable(&command.program)?;
+        self.create_data_directories()?;
+        let sockets = self.bind_service_sockets()?;
+        command.spawn_with_sockets(&sockets)
+    }
+
+    /// Legt die Verzeichnisse aus [`data_directories`](Self::data_directories) an.
+    pub(crate) fn create_data_directories(&self) -> Result<(), BundleError> {
+        let dirs = self.data_directories()?;
+        for dir in [dirs.data, dirs.cache, dirs.config].into_iter().flatten() {
+            std::fs::create_dir_all(dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directory_identifier_must_be_single_component() {
+        assert!(check_directory_identifier("org.example.app").is_ok());
+        for identifier in ["", ".", "..", "../../x", "/etc/foo", "a/b", "a b"] {
+            assert!(check_directory_identifier(identifier).is_err(), "{:?}", identifier);
+        }
+    }
+
+    #[test]
+    fn child_starts_with_default_signal_state() {
+        // Blockiert SIGUSR1 im aufrufenden Thread; SIGPIPE ignoriert die Rust-Laufzeit bereits
+        let mut blocked: libc::sigset_t = unsafe { std::mem::zeroed() };
+        let mut previous: libc::sigset_t = unsafe { std::mem::zeroed() };
+        unsafe {
+            libc::sigemptyset(&mut blocked);
+            libc::sigaddset(&mut blocked, libc::SIGUSR1);
+            libc::pthread_sigmask(libc::SIG_BLOCK, &blocked, &mut previous);
+        }
+
+        let command = LaunchCommand {
+            program: PathBuf::from("/bin/sh"),
+            args: vec![
+                "-c".into(),
+                r#"blk=$(sed -n 's/^SigBlk:\t//p' /proc/$$/status)
+                   ign=$(sed -n 's/^SigIgn:\t//p' /proc/$$/status)
+                   [ $((0x$blk)) -eq 0 ] && [ $((0x$ign & 0x1000)) -eq 0 ]"#
+                    .into(),
+            ],
+            env: Vec::new(),
+            working_directory: PathBuf::from("/"),
+        };
+        let status = command.spawn().and_then(|mut process| process.wait());
+        unsafe { libc::pthread_sigmask(libc::SIG_SETMASK, &previous, std::ptr::null_mut()) };
+        assert!(status.unwrap().success());
+    }
+}
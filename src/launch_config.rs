@@ -0,0 +1,193 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::launch::{
+    ENV_BUNDLE_CACHE_DIR, ENV_BUNDLE_CONFIG_DIR, ENV_BUNDLE_CONTENT_PATH, ENV_BUNDLE_DATA_DIR,
+    ENV_BUNDLE_EXECUTABLE, ENV_BUNDLE_IDENTIFIER, ENV_BUNDLE_PATH,
+};
+use crate::{BundleError, BundleValidationError, LaunchConfig};
+
+/// Platzhalter, die in `launch`-Werten als `${NAME}` erlaubt sind.
+/// Sie entsprechen den `BUNDLE_*`-Variablen, die jedem gestarteten Bundle übergeben werden.
+pub const LAUNCH_PLACEHOLDERS: &[&str] = &[
+    ENV_BUNDLE_IDENTIFIER,
+    ENV_BUNDLE_PATH,
+    ENV_BUNDLE_CONTENT_PATH,
+    ENV_BUNDLE_EXECUTABLE,
+    ENV_BUNDLE_DATA_DIR,
+    ENV_BUNDLE_CACHE_DIR,
+    ENV_BUNDLE_CONFIG_DIR,
+];
+
+/// Teil eines Werts: Literal oder Platzhalter.
+enum Segment<'a> {
+    Literal(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Zerlegt `template` in Literale und `${NAME}`-Platzhalter. `$$` steht für ein `$`.
+fn segments(template: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(at) = rest.find('$') {
+        segments.push(Segment::Literal(&rest[..at]));
+        let after = &rest[at + 1..];
+        if let Some(after) = after.strip_prefix('$') {
+            segments.push(Segment::Literal("$"));
+            rest = after;
+        } else if let Some(inner) = after.strip_prefix('{') {
+            let end = inner
+                .find('}')
+                .ok_or_else(|| format!("unterminated placeholder in '{}'", template))?;
+            segments.push(Segment::Placeholder(&inner[..end]));
+            rest = &inner[end + 1..];
+        } else {
+            segments.push(Segment::Literal("$"));
+            rest = after;
+        }
+    }
+    segments.push(Segment::Literal(rest));
+    Ok(segments)
+}
+
+/// Prüft, dass `template` nur bekannte Platzhalter enthält.
+pub(crate) fn check_template(template: &str) -> Result<(), String> {
+    for segment in segments(template)? {
+        if let Segment::Placeholder(name) = segment
+            && !LAUNCH_PLACEHOLDERS.contains(&name)
+        {
+            return Err(format!("unknown placeholder '${{{}}}' in '{}'", name, template));
+        }
+    }
+    Ok(())
+}
+
+/// Namen aller Platzhalter in `template`, in Reihenfolge.
+pub(crate) fn placeholders(template: &str) -> Result<Vec<&str>, String> {
+    Ok(segments(template)?
+        .into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Placeholder(name) => Some(name),
+            Segment::Literal(_) => None,
+        })
+        .collect())
+}
+
+/// Setzt für jeden Platzhalter einen Beispielwert ein, um die Form des
+/// erweiterten Werts schon ohne Bundle prüfen zu können. Die Verzeichnisse
+/// sind absolute Pfade, der Bezeichner ein einfacher Name.
+pub(crate) fn sample_expansion(template: &str) -> Result<String, String> {
+    check_template(template)?;
+    let mut expanded = String::new();
+    for segment in segments(template)? {
+        match segment {
+            Segment::Literal(text) => expanded.push_str(text),
+            Segment::Placeholder(ENV_BUNDLE_IDENTIFIER) => expanded.push_str("identifier"),
+            Segment::Placeholder(name) => {
+                expanded.push('/');
+                expanded.push_str(&name.to_ascii_lowercase());
+            }
+        }
+    }
+    Ok(expanded)
+}
+
+/// Ersetzt die Platzhalter in `template` durch die Werte aus `env`.
+pub(crate) fn expand(template: &str, env: &[(OsString, OsString)]) -> Result<OsString, BundleError> {
+    check_template(template).map_err(BundleError::InvalidFormat)?;
+    let mut expanded = OsString::new();
+    for segment in segments(template).map_err(BundleError::InvalidFormat)? {
+        match segment {
+            Segment::Literal(text) => expanded.push(text),
+            Segment::Placeholder(name) => {
+                let value = env
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value)
+                    .ok_or_else(|| BundleError::NotFound(format!("no value for placeholder '${{{}}}'", name)))?;
+                expanded.push(value);
+            }
+        }
+    }
+    Ok(expanded)
+}
+
+impl LaunchConfig {
+    /// Prüft alle Werte auf unbekannte oder unvollständige Platzhalter
+    /// und die Variablennamen auf Gültigkeit.
+    pub fn validate(&self) -> Result<(), BundleValidationError> {
+        let invalid = BundleValidationError::InvalidFormat;
+        for argument in &self.arguments {
+            check_template(argument).map_err(|e| invalid(format!("launch.arguments: {}", e)))?;
+        }
+        for (key, value) in &self.environment {
+            if key.is_empty() || key.contains('=') || key.contains('\0') {
+                return Err(invalid(format!("launch.environment: invalid variable name '{}'", key)));
+            }
+            check_template(value).map_err(|e| invalid(format!("launch.environment.{}: {}", key, e)))?;
+        }
+        if let Some(dir) = &self.working_directory {
+            check_template(dir).map_err(|e| invalid(format!("launch.working_directory: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    /// Löst das Arbeitsverzeichnis auf; relative Pfade beziehen sich auf `content_path`.
+    pub(crate) fn resolve_working_directory(
+        &self,
+        content_path: &Path,
+        env: &[(OsString, OsString)],
+    ) -> Result<PathBuf, BundleError> {
+        match &self.working_directory {
+            Some(dir) => Ok(content_path.join(expand(dir, env)?)),
+            None => Ok(content_path.to_path_buf()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env() -> Vec<(OsString, OsString)> {
+        vec![
+            (ENV_BUNDLE_IDENTIFIER.into(), "org.example.app".into()),
+            (ENV_BUNDLE_DATA_DIR.into(), "/home/user/.local/share/org.example.app".into()),
+        ]
+    }
+
+    #[test]
+    fn placeholders_are_expanded() {
+        assert_eq!(
+            expand("--data=${BUNDLE_DATA_DIR}/db", &env()).unwrap(),
+            "--data=/home/user/.local/share/org.example.app/db"
+        );
+        assert_eq!(expand("${BUNDLE_IDENTIFIER}${BUNDLE_IDENTIFIER}", &env()).unwrap(), "org.example.apporg.example.app");
+        assert_eq!(expand("plain", &env()).unwrap(), "plain");
+    }
+
+    #[test]
+    fn dollar_escapes_and_lone_dollars_stay_literal() {
+        assert_eq!(expand("$$", &env()).unwrap(), "$");
+        assert_eq!(expand("$${BUNDLE_IDENTIFIER}", &env()).unwrap(), "${BUNDLE_IDENTIFIER}");
+        assert_eq!(expand("cost: 5$", &env()).unwrap(), "cost: 5$");
+        assert_eq!(expand("$HOME", &env()).unwrap(), "$HOME");
+    }
+
+    #[test]
+    fn unknown_placeholder_is_rejected() {
+        assert!(matches!(expand("${HOME}", &env()), Err(BundleError::InvalidFormat(_))));
+        assert!(check_template("${BUNDLE_NOPE}").is_err());
+    }
+
+    #[test]
+    fn unterminated_placeholder_is_rejected() {
+        assert!(matches!(expand("${BUNDLE_DATA_DIR", &env()), Err(BundleError::InvalidFormat(_))));
+        assert!(check_template("prefix ${").is_err());
+    }
+
+    #[test]
+    fn known_placeholder_without_value_is_not_found() {
+        assert!(matches!(expand("${BUNDLE_CACHE_DIR}", &env()), Err(BundleError::NotFound(_))));
+    }
+}
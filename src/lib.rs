@@ -1,6 +1,10 @@
 mod bundle_load_info_config;  // neues Modul hinzufügen
 pub use bundle_load_info_config::{bundle_load_info_config, get_loaded_bundle_info_config}; // oder wie du die Funktion nennst
 
+mod xdg;
+
+mod atomic_file;
+
 mod bundle_info_config_file;
 pub use bundle_info_config_file::*;
 
@@ -16,6 +20,79 @@ pub use is_launched_from_bundle::is_launched_from_bundle;
 mod find_bundle_root;
 pub use find_bundle_root::find_bundle_root;
 
+mod bundle;
+pub use bundle::{Bundle, BundleKind};
+
+mod localization;
+pub use localization::{preferred_languages, localization_chain};
+
+mod framework_bundle;
+pub use framework_bundle::{FrameworkBundle, FrameworkVersion, FRAMEWORK_VERSIONS_DIR, FRAMEWORK_CURRENT_VERSION};
+
+mod framework_loader;
+pub use framework_loader::{LoadedFramework, Symbol};
+
+mod registry;
+pub use registry::{
+    BundleRegistry, RegisteredBundle, SearchRoot, SearchScope, default_cache_path, REGISTRY_CACHE_SCHEMA_VERSION,
+    BundleEvent, BundleWatcher, DEFAULT_WATCH_DEBOUNCE,
+};
+
+mod handler_preferences;
+pub use handler_preferences::HandlerPreferences;
+
+mod url_handlers;
+pub use url_handlers::{url_scheme, UrlSchemeConflict, UrlSchemeHandlers};
+
+mod document_handlers;
+pub use document_handlers::{file_extensions, DocumentHandler};
+
+mod document_identification;
+pub use document_identification::{DocumentTypeMatch, MatchReason, PLAIN_TEXT_MIME_TYPE, SNIFF_HEADER_LEN};
+
+mod launch;
+pub use launch::{
+    BundleDataDirectories, BundleProcess, LaunchCommand, ENV_BUNDLE_CACHE_DIR, ENV_BUNDLE_CONFIG_DIR, ENV_BUNDLE_CONTENT_PATH, ENV_BUNDLE_DATA_DIR,
+    ENV_BUNDLE_EXECUTABLE, ENV_BUNDLE_IDENTIFIER, ENV_BUNDLE_PATH,
+};
+
+mod launch_config;
+pub use launch_config::LAUNCH_PLACEHOLDERS;
+
+mod executables;
+pub use executables::BundleExecutable;
+
+mod toolset_shims;
+pub use toolset_shims::{Shim, ShimConflict, ShimDirectory, ShimKind, ShimReport};
+
+mod toolset_support_files;
+pub use toolset_support_files::{
+    ManPage, Shell, ShellCompletion, SupportFileConflict, SupportFileDirectories, SupportFileReport, COMPLETIONS_DIRECTORY,
+    MAN_PAGES_DIRECTORY,
+};
+
+mod systemd_unit;
+pub use systemd_unit::{systemd_service_unit, systemd_socket_units, SystemdScope};
+
+mod service_config;
+pub use service_config::{DEFAULT_HEALTH_CHECK_TIMEOUT, SOCKET_PATH_FORBIDDEN_PLACEHOLDERS};
+
+mod socket_activation;
+pub use socket_activation::{
+    listen_fds, ListenSockets, ENV_LISTEN_FDNAMES, ENV_LISTEN_FDS, ENV_LISTEN_PID, SD_LISTEN_FDS_START,
+};
+
+mod embedded_bundles;
+pub use embedded_bundles::{EmbeddedBundles, IdentifierCollision, EMBEDDED_BUNDLE_DIRECTORIES};
+
+mod localized_info;
+pub use localized_info::{LocalizedInfoOverrides, LOCALIZED_INFO_FILE};
+
+mod localized_strings;
+pub use localized_strings::{StringTable, DEFAULT_STRING_TABLE};
+
+mod resources;
+
 mod is_bundle_at_path;
 pub use is_bundle_at_path::is_bundle_at_path;
 
@@ -26,7 +103,9 @@ mod get_current_launched_bundle_path;
 pub use get_current_launched_bundle_path::get_current_launched_bundle_path;
 
 mod validate_bundle;
-pub use validate_bundle::validate_bundle;
+pub use validate_bundle::{validate_bundle, detect_elf_architecture, is_x86_64, is_arm64, Architecture};
 
 mod entitlements;
-pub use entitlements::EntitlementType;
\ No newline at end of file
+pub use entitlements::EntitlementType;
+
+pub mod ffi;
@@ -0,0 +1,108 @@
+use std::env;
+
+/// Ermittelt die bevorzugten Sprachen des Benutzers in absteigender Priorität.
+///
+/// Ausgewertet werden wie bei gettext `LANGUAGE` (durch `:` getrennte Liste) sowie
+/// die erste gesetzte Variable aus `LC_ALL`, `LC_MESSAGES` und `LANG`.
+/// Kodierung und Modifier werden entfernt (`de_DE.UTF-8@euro` → `de_DE`), und auf
+/// jede regionale Variante folgt die reine Sprache (`de_DE`, `de`).
+/// Die Locales `C` und `POSIX` werden ignoriert.
+pub fn preferred_languages() -> Vec<String> {
+    let var = |name: &str| env::var(name).ok().filter(|v| !v.is_empty());
+    let locale = var("LC_ALL").or_else(|| var("LC_MESSAGES")).or_else(|| var("LANG"));
+    languages_from_env(var("LANGUAGE").as_deref(), locale.as_deref())
+}
+
+/// Bildet die Fallback-Kette für eine Lokalisierung: zuerst die bevorzugten
+/// Sprachen in ihrer Reihenfolge, danach die Entwicklungssprache des Bundles
+/// (jeweils mit der reinen Sprache als Rückfall für regionale Varianten).
+pub fn localization_chain(preferred: &[String], development_language: Option<&str>) -> Vec<String> {
+    let mut chain = Vec::new();
+    let tags = preferred
+        .iter()
+        .filter_map(|lang| normalize_language(lang))
+        .chain(development_language.and_then(normalize_language));
+    for tag in tags {
+        push_with_base(&mut chain, tag);
+    }
+    chain
+}
+
+/// Baut die Sprachliste aus `LANGUAGE` und der wirksamen Locale auf.
+pub(crate) fn languages_from_env(language: Option<&str>, locale: Option<&str>) -> Vec<String> {
+    let mut languages = Vec::new();
+
+    // gettext ignoriert LANGUAGE, wenn die Locale "C" ist
+    let locale_tag = locale.and_then(normalize_language);
+    if locale.is_some() && locale_tag.is_none() {
+        return languages;
+    }
+
+    let candidates = language
+        .into_iter()
+        .flat_map(|list| list.split(':'))
+        .filter_map(normalize_language)
+        .chain(locale_tag);
+
+    for tag in candidates {
+        push_with_base(&mut languages, tag);
+    }
+    languages
+}
+
+/// Entfernt Kodierung und Modifier einer Locale und vereinheitlicht `-` zu `_`.
+/// Gibt `None` für leere Werte sowie `C` und `POSIX` zurück.
+pub(crate) fn normalize_language(locale: &str) -> Option<String> {
+    let tag = locale
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .replace('-', "_");
+    match tag.as_str() {
+        "" | "C" | "POSIX" => None,
+        _ => Some(tag),
+    }
+}
+
+/// Fügt `tag` und anschließend die reine Sprache ohne Region hinzu, ohne Duplikate.
+pub(crate) fn push_with_base(languages: &mut Vec<String>, tag: String) {
+    let base = tag.split('_').next().map(str::to_string);
+    for candidate in std::iter::once(tag).chain(base) {
+        if !languages.contains(&candidate) {
+            languages.push(candidate);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_falls_back_to_base_language_then_development_language() {
+        let preferred = languages_from_env(None, Some("de_AT.UTF-8"));
+        assert_eq!(preferred, ["de_AT", "de"]);
+        assert_eq!(localization_chain(&preferred, Some("en")), ["de_AT", "de", "en"]);
+    }
+
+    #[test]
+    fn language_list_comes_before_locale() {
+        let preferred = languages_from_env(Some("fr_CA:pt-BR@latin"), Some("de_DE.UTF-8@euro"));
+        assert_eq!(preferred, ["fr_CA", "fr", "pt_BR", "pt", "de_DE", "de"]);
+    }
+
+    #[test]
+    fn c_locale_disables_language_list() {
+        assert!(languages_from_env(Some("de"), Some("C")).is_empty());
+        assert!(languages_from_env(Some("de"), Some("POSIX.UTF-8")).is_empty());
+        assert_eq!(languages_from_env(Some("de"), None), ["de"]);
+    }
+
+    #[test]
+    fn chain_has_no_duplicates() {
+        let preferred = ["en_US".to_string(), "en".to_string()];
+        assert_eq!(localization_chain(&preferred, Some("en_GB")), ["en_US", "en", "en_GB"]);
+        assert_eq!(localization_chain(&[], None), Vec::<String>::new());
+    }
+}
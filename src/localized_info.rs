@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::{localization_chain, preferred_languages, Bundle, BundleError, BundleInfoConfigFile};
+
+/// Dateiname der lokalisierten Info.json-Werte in `Content/Resources/<sprache>.lproj/`.
+pub const LOCALIZED_INFO_FILE: &str = "Info.json";
+
+/// Lokalisierte Überschreibungen für Anzeige-Werte der Info.json.
+///
+/// ```json
+/// {
+///   "name": "Beispiel",
+///   "url_schemes": { "demo": "Beispiel-Links" },
+///   "document_types": { "Text": "Textdokument" }
+/// }
+/// ```
+/// `url_schemes` ist nach `UrlScheme::scheme` und `document_types` nach dem
+/// unlokalisierten `DocumentType::name` indiziert.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LocalizedInfoOverrides {
+    pub name: Option<String>,
+    pub url_schemes: HashMap<String, String>,
+    pub document_types: HashMap<String, String>,
+}
+
+fn load_overrides(path: &Path) -> Result<Option<LocalizedInfoOverrides>, BundleError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let data = std::fs::read(path)?;
+    let overrides = serde_json::from_slice(&data).map_err(|e| {
+        BundleError::InvalidFormat(format!("Failed to parse '{}': {}", path.display(), e))
+    })?;
+    Ok(Some(overrides))
+}
+
+impl Bundle {
+    /// Liest die Überschreibungen für `language`, oder `None`, falls keine vorhanden sind.
+    pub fn localized_info_overrides(&self, language: &str) -> Result<Option<LocalizedInfoOverrides>, BundleError> {
+        let path = self
+            .resources_path()
+            .join(format!("{}.lproj", language))
+            .join(LOCALIZED_INFO_FILE);
+        load_overrides(&path)
+    }
+
+    /// Liefert die Info.json mit lokalisierten Werten für `name`,
+    /// `UrlScheme::description` und `DocumentType::name`.
+    ///
+    /// Ohne `locale` werden die bevorzugten Sprachen des Benutzers verwendet.
+    /// Die Fallback-Kette entspricht der von [`Bundle::localized_string`]; für
+    /// jeden Wert gewinnt die erste Sprache, die ihn überschreibt, sonst bleibt
+    /// der Basiswert erhalten.
+    pub fn localized_info(&self, locale: Option<&str>) -> Result<BundleInfoConfigFile, BundleError> {
+        let base = self.info()?;
+        let mut info = base.clone();
+        let requested = match locale {
+            Some(locale) => vec![locale.to_string()],
+            None => preferred_languages(),
+        };
+        let chain = localization_chain(&requested, base.development_language.as_deref());
+
+        // Rückwärts anwenden, damit Sprachen mit höherer Priorität zuletzt schreiben
+        for language in chain.iter().rev() {
+            let Some(overrides) = self.localized_info_overrides(language)? else {
+                continue;
+            };
+            if let Some(name) = overrides.name {
+                info.name = name;
+            }
+            for url_scheme in &mut info.url_schemes {
+                if let Some(description) = overrides.url_schemes.get(&url_scheme.scheme) {
+                    url_scheme.description = description.clone();
+                }
+            }
+            let document_types = base.fibyos.document_types.iter().zip(&mut info.fibyos.document_types);
+            for (base_type, document_type) in document_types {
+                if let Some(name) = overrides.document_types.get(&base_type.name) {
+                    document_type.name = name.clone();
+                }
+            }
+        }
+        Ok(info)
+    }
+}
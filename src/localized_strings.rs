@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::{Bundle, BundleError};
+
+/// Name der Standardtabelle (`Localizable.json`).
+pub const DEFAULT_STRING_TABLE: &str = "Localizable";
+
+/// Inhalt einer String-Tabelle: Schlüssel → übersetzter Text.
+pub type StringTable = HashMap<String, String>;
+
+/// Lädt eine String-Tabelle. Eine fehlende Datei ergibt `Ok(None)`.
+fn load_string_table(path: &Path) -> Result<Option<StringTable>, BundleError> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let data = std::fs::read(path)?;
+    let table = serde_json::from_slice(&data).map_err(|e| {
+        BundleError::InvalidFormat(format!("Failed to parse string table '{}': {}", path.display(), e))
+    })?;
+    Ok(Some(table))
+}
+
+impl Bundle {
+    /// Liefert die String-Tabelle `table` für die Sprache `language`
+    /// (`Content/Resources/<language>.lproj/<table>.json`), oder `None`, falls sie fehlt.
+    /// Einmal gelesene Tabellen werden im Bundle zwischengespeichert.
+    pub fn string_table(&self, language: &str, table: Option<&str>) -> Result<Option<Arc<StringTable>>, BundleError> {
+        let path = self
+            .resources_path()
+            .join(format!("{}.lproj", language))
+            .join(format!("{}.json", table.unwrap_or(DEFAULT_STRING_TABLE)));
+
+        if let Some(cached) = self.string_tables.lock().unwrap().get(&path) {
+            return Ok(cached.clone());
+        }
+        let loaded = load_string_table(&path)?.map(Arc::new);
+        self.string_tables.lock().unwrap().insert(path, loaded.clone());
+        Ok(loaded)
+    }
+
+    /// Sucht den lokalisierten Text zu `key` in der Tabelle `table`
+    /// (Standard: `Localizable`). Die Sprachen werden in der Reihenfolge von
+    /// [`Bundle::preferred_localizations`] durchsucht. Tabellen, die nicht
+    /// gelesen werden können, werden übersprungen. Wird der Schlüssel nirgends
+    /// gefunden, wird er selbst zurückgegeben.
+    pub fn localized_string(&self, key: &str, table: Option<&str>) -> String {
+        self.preferred_localizations()
+            .iter()
+            .filter_map(|language| self.string_table(language, table).ok().flatten())
+            .find_map(|strings| strings.get(key).cloned())
+            .unwrap_or_else(|| key.to_string())
+    }
+}
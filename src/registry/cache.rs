@@ -0,0 +1,104 @@
+//! Persistenter Cache für den Registry-Index.
+//!
+//! Pro Bundle werden Pfad, Art und die geparste Info.json zusammen mit einem
+//! Stempel der Info.json (Inode, mtime, Größe) gespeichert. Stimmt der Stempel
+//! beim nächsten Scan überein, wird die Info.json nicht erneut gelesen.
+//! Beschädigte Cache-Dateien oder Dateien eines anderen Schemas werden
+//! verworfen und beim nächsten Schreiben ersetzt.
+
+use std::collections::HashMap;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::atomic_file::write_atomically;
+use crate::{xdg, BundleInfoConfigFile, BundleKind};
+
+/// Version des Cache-Formats. Bei inkompatiblen Änderungen erhöhen.
+pub const REGISTRY_CACHE_SCHEMA_VERSION: u32 = 6;
+
+/// Standardpfad: `$XDG_CACHE_HOME/bundle/registry.json` bzw. `~/.cache/bundle/registry.json`.
+pub fn default_cache_path() -> Option<PathBuf> {
+    xdg::cache_home().map(|cache| cache.join("bundle/registry.json"))
+}
+
+/// Änderungsstempel einer Datei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FileStamp {
+    inode: u64,
+    mtime_sec: i64,
+    mtime_nsec: i64,
+    size: u64,
+}
+
+impl FileStamp {
+    /// Stempel der Info.json des Bundles unter `bundle_path`.
+    pub(crate) fn of_info_file(bundle_path: &Path) -> Option<FileStamp> {
+        let meta = fs::metadata(bundle_path.join("Content/Info.json")).ok()?;
+        Some(FileStamp {
+            inode: meta.ino(),
+            mtime_sec: meta.mtime(),
+            mtime_nsec: meta.mtime_nsec(),
+            size: meta.size(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CacheEntry {
+    pub(crate) stamp: FileStamp,
+    pub(crate) kind: BundleKind,
+    pub(crate) info: BundleInfoConfigFile,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    schema: u32,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+/// Im Speicher gehaltener Cache-Inhalt.
+#[derive(Debug, Default)]
+pub(crate) struct RegistryCache {
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl RegistryCache {
+    /// Lädt den Cache. Fehlende, beschädigte oder veraltete Dateien ergeben einen leeren Cache.
+    pub(crate) fn load(path: &Path) -> RegistryCache {
+        let Ok(data) = fs::read(path) else {
+            return RegistryCache::default();
+        };
+        match serde_json::from_slice::<CacheFile>(&data) {
+            Ok(file) if file.schema == REGISTRY_CACHE_SCHEMA_VERSION => RegistryCache { entries: file.entries },
+            _ => RegistryCache::default(),
+        }
+    }
+
+    /// Liefert den Eintrag für `bundle_path`, sofern Stempel und Art noch stimmen.
+    pub(crate) fn lookup(&self, bundle_path: &Path, stamp: FileStamp, kind: BundleKind) -> Option<&CacheEntry> {
+        self.entries
+            .get(bundle_path)
+            .filter(|entry| entry.stamp == stamp && entry.kind == kind)
+    }
+
+    pub(crate) fn insert(&mut self, bundle_path: PathBuf, entry: CacheEntry) {
+        self.entries.insert(bundle_path, entry);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Schreibt den Cache atomar (temporäre Datei im selben Verzeichnis, dann `rename`).
+    pub(crate) fn store(self, path: &Path) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = CacheFile { schema: REGISTRY_CACHE_SCHEMA_VERSION, entries: self.entries };
+        let data = serde_json::to_vec(&file).map_err(std::io::Error::other)?;
+        write_atomically(path, &data, None)
+    }
+}
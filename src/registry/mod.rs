@@ -0,0 +1,270 @@
+//! Systemweites Verzeichnis installierter Bundles.
+//!
+//! Die Registry durchsucht konfigurierbare Suchwurzeln nach Bundles, lädt deren
+//! Info.json und indiziert sie nach `identifier`, Bundle-Art und `team_id`.
+//!
+//! Ist derselbe Bezeichner mehrfach installiert, gilt:
+//! 1. Eine Wurzel mit höherem [`SearchScope`] verdeckt niedrigere
+//!    (`User` vor `Local` vor `System`).
+//! 2. Innerhalb derselben Stufe gewinnt die zuerst konfigurierte Wurzel.
+//! 3. Innerhalb derselben Wurzel gewinnt der lexikographisch kleinste Pfad.
+//!
+//! Verdeckte Bundles bleiben über [`BundleRegistry::all_with_identifier`] abrufbar.
+//!
+//! Mit [`BundleRegistry::scan_cached`] wird der Index zusätzlich in einer
+//! Cache-Datei abgelegt, sodass unveränderte Bundles nicht erneut geparst werden.
+//! [`BundleRegistry::watch`] meldet Änderungen an den Suchwurzeln live per inotify.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+mod cache;
+pub use cache::{default_cache_path, REGISTRY_CACHE_SCHEMA_VERSION};
+use cache::{CacheEntry, FileStamp, RegistryCache};
+
+mod watcher;
+pub use watcher::{BundleEvent, BundleWatcher, DEFAULT_WATCH_DEBOUNCE};
+
+use crate::bundle_load_info_config::load_bundle_info_file;
+use crate::{xdg, Bundle, BundleError, BundleInfoConfigFile, BundleKind};
+
+/// Stufe einer Suchwurzel. Spätere Varianten haben Vorrang.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SearchScope {
+    /// Mit dem System ausgelieferte Bundles.
+    System,
+    /// Vom Administrator lokal installierte Bundles.
+    Local,
+    /// Bundles im Home-Verzeichnis des Benutzers.
+    User,
+}
+
+/// Ein Verzeichnis, dessen direkte Einträge nach Bundles durchsucht werden.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchRoot {
+    pub scope: SearchScope,
+    pub path: PathBuf,
+}
+
+impl SearchRoot {
+    pub fn new(scope: SearchScope, path: impl Into<PathBuf>) -> SearchRoot {
+        SearchRoot { scope, path: path.into() }
+    }
+
+    /// Standard-Suchwurzeln:
+    /// - `System`: `/usr/share/bundles`
+    /// - `Local`: `/usr/local/share/bundles`
+    /// - `User`: `$XDG_DATA_HOME/bundles` bzw. `~/.local/share/bundles`
+    pub fn defaults() -> Vec<SearchRoot> {
+        let mut roots = vec![
+            SearchRoot::new(SearchScope::System, "/usr/share/bundles"),
+            SearchRoot::new(SearchScope::Local, "/usr/local/share/bundles"),
+        ];
+        if let Some(data_home) = xdg::data_home() {
+            roots.push(SearchRoot::new(SearchScope::User, data_home.join("bundles")));
+        }
+        roots
+    }
+}
+
+/// Ein in der Registry gefundenes Bundle mit geparster Info.json.
+#[derive(Debug, Clone)]
+pub struct RegisteredBundle {
+    pub path: PathBuf,
+    pub kind: BundleKind,
+    pub scope: SearchScope,
+    pub info: BundleInfoConfigFile,
+}
+
+impl RegisteredBundle {
+    /// Bezeichner aus der Info.json.
+    pub fn identifier(&self) -> &str {
+        &self.info.identifier
+    }
+
+    /// Öffnet das Bundle als [`Bundle`]-Handle.
+    pub fn open(&self) -> Result<Bundle, BundleError> {
+        Bundle::open(&self.path)
+    }
+}
+
+/// Index aller Bundles unter den konfigurierten Suchwurzeln.
+#[derive(Debug)]
+pub struct BundleRegistry {
+    roots: Vec<SearchRoot>,
+    cache_path: Option<PathBuf>,
+    entries: Vec<RegisteredBundle>,
+    /// Indizes in `entries` je Bezeichner; das erste Element ist das wirksame Bundle.
+    by_identifier: HashMap<String, Vec<usize>>,
+    errors: Vec<(PathBuf, BundleError)>,
+}
+
+impl BundleRegistry {
+    /// Durchsucht `roots` und baut den Index auf.
+    pub fn scan(roots: Vec<SearchRoot>) -> BundleRegistry {
+        BundleRegistry::build(roots, None)
+    }
+
+    /// Wie [`BundleRegistry::scan`], nutzt und aktualisiert aber die Cache-Datei `cache_path`.
+    pub fn scan_cached(roots: Vec<SearchRoot>, cache_path: impl Into<PathBuf>) -> BundleRegistry {
+        BundleRegistry::build(roots, Some(cache_path.into()))
+    }
+
+    fn build(roots: Vec<SearchRoot>, cache_path: Option<PathBuf>) -> BundleRegistry {
+        let mut registry = BundleRegistry {
+            roots,
+            cache_path,
+            entries: Vec::new(),
+            by_identifier: HashMap::new(),
+            errors: Vec::new(),
+        };
+        registry.rescan();
+        registry
+    }
+
+    /// Durchsucht die Standard-Suchwurzeln aus [`SearchRoot::defaults`] und
+    /// nutzt den Cache unter [`default_cache_path`], sofern dieser bestimmbar ist.
+    pub fn scan_default() -> BundleRegistry {
+        BundleRegistry::build(SearchRoot::defaults(), default_cache_path())
+    }
+
+    /// Verwirft den Index und durchsucht alle Suchwurzeln erneut.
+    /// Mit Cache wird die Info.json nur für neue oder geänderte Bundles gelesen;
+    /// die Cache-Datei wird nur geschrieben, wenn sich etwas geändert hat.
+    /// Fehler beim Schreiben des Caches werden ignoriert.
+    pub fn rescan(&mut self) {
+        let old_cache = match &self.cache_path {
+            Some(path) => RegistryCache::load(path),
+            None => RegistryCache::default(),
+        };
+        let mut new_cache = RegistryCache::default();
+        let mut changed = false;
+
+        let mut entries = Vec::new();
+        let mut errors = Vec::new();
+        for root in &self.roots {
+            for path in bundle_candidates(&root.path) {
+                let Some(kind) = BundleKind::detect(&path) else {
+                    continue;
+                };
+                let stamp = FileStamp::of_info_file(&path);
+                let cached = stamp.and_then(|stamp| old_cache.lookup(&path, stamp, kind));
+                let info = match cached {
+                    Some(entry) => entry.info.clone(),
+                    None => match load_bundle_info_file(&path) {
+                        Ok(info) => {
+                            changed = true;
+                            info
+                        }
+                        Err(e) => {
+                            errors.push((path, e));
+                            continue;
+                        }
+                    },
+                };
+                if let Some(stamp) = stamp {
+                    new_cache.insert(path.clone(), CacheEntry { stamp, kind, info: info.clone() });
+                }
+                entries.push(RegisteredBundle { path, kind, scope: root.scope, info });
+            }
+        }
+
+        // Entfernte Bundles verändern den Cache ebenfalls
+        changed |= new_cache.len() != old_cache.len();
+        if let (true, Some(cache_path)) = (changed, &self.cache_path) {
+            let _ = new_cache.store(cache_path);
+        }
+        self.set_entries(entries, errors);
+    }
+
+    /// Ersetzt den Inhalt und baut die Indizes neu auf. `entries` muss in
+    /// Wurzel-Reihenfolge und innerhalb einer Wurzel nach Pfad sortiert sein.
+    pub(crate) fn set_entries(&mut self, entries: Vec<RegisteredBundle>, errors: Vec<(PathBuf, BundleError)>) {
+        let mut by_identifier: HashMap<String, Vec<usize>> = HashMap::new();
+        for (index, entry) in entries.iter().enumerate() {
+            by_identifier.entry(entry.info.identifier.clone()).or_default().push(index);
+        }
+        // Stabile Sortierung: höhere Stufe zuerst, sonst bleibt die Suchreihenfolge
+        for indices in by_identifier.values_mut() {
+            indices.sort_by_key(|&i| std::cmp::Reverse(entries[i].scope));
+        }
+        self.entries = entries;
+        self.by_identifier = by_identifier;
+        self.errors = errors;
+    }
+
+    /// Pfad der Cache-Datei, falls die Registry einen Cache verwendet.
+    pub fn cache_path(&self) -> Option<&Path> {
+        self.cache_path.as_deref()
+    }
+
+    /// Konfigurierte Suchwurzeln.
+    pub fn roots(&self) -> &[SearchRoot] {
+        &self.roots
+    }
+
+    /// Das wirksame Bundle mit dem Bezeichner `identifier`.
+    pub fn get(&self, identifier: &str) -> Option<&RegisteredBundle> {
+        self.by_identifier
+            .get(identifier)
+            .and_then(|indices| indices.first())
+            .map(|&i| &self.entries[i])
+    }
+
+    /// Alle Installationen von `identifier`, das wirksame Bundle zuerst,
+    /// danach die verdeckten in absteigender Priorität.
+    pub fn all_with_identifier(&self, identifier: &str) -> Vec<&RegisteredBundle> {
+        self.by_identifier
+            .get(identifier)
+            .map(|indices| indices.iter().map(|&i| &self.entries[i]).collect())
+            .unwrap_or_default()
+    }
+
+    /// Alle wirksamen (nicht verdeckten) Bundles in Suchreihenfolge.
+    pub fn bundles(&self) -> impl Iterator<Item = &RegisteredBundle> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(index, entry)| self.by_identifier[&entry.info.identifier].first() == Some(index))
+            .map(|(_, entry)| entry)
+    }
+
+    /// Alle wirksamen Bundles der Art `kind`.
+    pub fn bundles_of_kind(&self, kind: BundleKind) -> impl Iterator<Item = &RegisteredBundle> {
+        self.bundles().filter(move |entry| entry.kind == kind)
+    }
+
+    /// Alle wirksamen Bundles mit der Team-ID `team_id` aus der Code-Signatur.
+    pub fn bundles_for_team<'a>(&'a self, team_id: &'a str) -> impl Iterator<Item = &'a RegisteredBundle> {
+        self.bundles()
+            .filter(move |entry| entry.info.security.code_signature.team_id == team_id)
+    }
+
+    /// Bundles, deren Info.json beim letzten Scan nicht geladen werden konnte.
+    pub fn errors(&self) -> &[(PathBuf, BundleError)] {
+        &self.errors
+    }
+}
+
+/// Direkte Einträge von `root`, nach Pfad sortiert. Fehlt die Wurzel, ist die Liste leer.
+pub(crate) fn bundle_candidates(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.filter_map(|entry| entry.ok().map(|e| e.path())).collect();
+    paths.sort();
+    paths
+}
+
+impl Bundle {
+    /// Öffnet das installierte Bundle mit dem Bezeichner `identifier` aus den
+    /// Standard-Suchwurzeln.
+    pub fn with_identifier(identifier: &str) -> Result<Bundle, BundleError> {
+        let registry = BundleRegistry::scan_default();
+        match registry.get(identifier) {
+            Some(entry) => entry.open(),
+            None => Err(BundleError::NotFound(format!("no bundle with identifier '{}'", identifier))),
+        }
+    }
+}
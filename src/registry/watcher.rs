@@ -0,0 +1,328 @@
+//! Live-Überwachung der Suchwurzeln per inotify.
+//!
+//! Beobachtet werden die Suchwurzeln selbst sowie jedes Bundle rekursiv mit
+//! allen Unterverzeichnissen; neu angelegte Verzeichnisse werden sofort
+//! nachgetragen. Jede Aktivität an einem Bundle, auch tief darin (z.B. in
+//! `Content/bin/`), setzt eine Wartezeit (Debounce) neu; erst wenn diese ohne
+//! weitere Änderungen abläuft, wird das Bundle erneut geprüft. Ein halb
+//! kopiertes Bundle wird so nicht gemeldet, solange noch Dateien hineinkommen.
+//! Symlinks innerhalb eines Bundles werden nicht verfolgt.
+
+use std::collections::HashMap;
+use std::ffi::{CString, OsStr};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::bundle_load_info_config::load_bundle_info_file;
+use crate::{BundleError, BundleKind};
+
+use super::cache::FileStamp;
+use super::{bundle_candidates, BundleRegistry, SearchRoot};
+
+/// Standard-Wartezeit, bevor ein verändertes Bundle erneut geprüft wird.
+pub const DEFAULT_WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+const ROOT_MASK: u32 = libc::IN_CREATE
+    | libc::IN_DELETE
+    | libc::IN_MOVED_FROM
+    | libc::IN_MOVED_TO
+    | libc::IN_ATTRIB
+    | libc::IN_ONLYDIR;
+const BUNDLE_MASK: u32 = ROOT_MASK | libc::IN_CLOSE_WRITE | libc::IN_MODIFY;
+
+/// Änderung an einem installierten Bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleEvent {
+    /// Ein gültiges Bundle ist neu unter einer Suchwurzel erschienen.
+    BundleAdded { identifier: String, path: PathBuf },
+    /// Ein bekanntes Bundle ist verschwunden oder nicht mehr gültig.
+    BundleRemoved { identifier: String, path: PathBuf },
+    /// Die Info.json eines bekannten Bundles hat sich geändert.
+    InfoChanged { identifier: String, path: PathBuf },
+}
+
+#[derive(Debug, Clone)]
+enum Watch {
+    /// Suchwurzel; Ereignisse beziehen sich auf den benannten Eintrag.
+    Root(PathBuf),
+    /// Verzeichnis `dir` innerhalb von `bundle` (oder das Bundle-Verzeichnis
+    /// selbst); Ereignisse betreffen das Bundle.
+    Bundle { bundle: PathBuf, dir: PathBuf },
+}
+
+#[derive(Debug, Clone)]
+struct KnownBundle {
+    identifier: String,
+    stamp: Option<FileStamp>,
+}
+
+/// inotify-basierter Beobachter für die Suchwurzeln einer Registry.
+#[derive(Debug)]
+pub struct BundleWatcher {
+    fd: OwnedFd,
+    debounce: Duration,
+    watches: HashMap<i32, Watch>,
+    known: HashMap<PathBuf, KnownBundle>,
+    pending: HashMap<PathBuf, Instant>,
+    roots: Vec<PathBuf>,
+}
+
+impl BundleRegistry {
+    /// Erzeugt einen [`BundleWatcher`] für die Suchwurzeln dieser Registry.
+    pub fn watch(&self, debounce: Duration) -> Result<BundleWatcher, BundleError> {
+        BundleWatcher::new(self.roots(), debounce)
+    }
+}
+
+impl BundleWatcher {
+    /// Beginnt mit der Überwachung von `roots`. Bereits vorhandene Bundles gelten
+    /// als bekannt und erzeugen kein `BundleAdded`. Fehlende Wurzeln werden übersprungen.
+    pub fn new(roots: &[SearchRoot], debounce: Duration) -> Result<BundleWatcher, BundleError> {
+        let raw = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if raw < 0 {
+            return Err(BundleError::IoError(std::io::Error::last_os_error()));
+        }
+        let mut watcher = BundleWatcher {
+            fd: unsafe { OwnedFd::from_raw_fd(raw) },
+            debounce,
+            watches: HashMap::new(),
+            known: HashMap::new(),
+            pending: HashMap::new(),
+            roots: roots.iter().map(|root| root.path.clone()).collect(),
+        };
+
+        for root in watcher.roots.clone() {
+            if watcher.add_watch(&root, ROOT_MASK, Watch::Root(root.clone())).is_err() {
+                continue;
+            }
+            for path in bundle_candidates(&root) {
+                if let Some(known) = valid_bundle(&path) {
+                    watcher.watch_bundle(&path);
+                    watcher.known.insert(path, known);
+                }
+            }
+        }
+        Ok(watcher)
+    }
+
+    fn add_watch(&mut self, path: &Path, mask: u32, watch: Watch) -> Result<(), BundleError> {
+        let c_path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|_| BundleError::InvalidFormat("path contains a NUL byte".into()))?;
+        let wd = unsafe { libc::inotify_add_watch(self.fd.as_raw_fd(), c_path.as_ptr(), mask) };
+        if wd < 0 {
+            return Err(BundleError::IoError(std::io::Error::last_os_error()));
+        }
+        self.watches.insert(wd, watch);
+        Ok(())
+    }
+
+    /// Beobachtet das Bundle-Verzeichnis mit allen Unterverzeichnissen.
+    fn watch_bundle(&mut self, path: &Path) {
+        self.watch_tree(path, path);
+    }
+
+    /// Beobachtet `dir` und alle Verzeichnisse darunter für das Bundle `bundle`.
+    /// Symlinks auf Verzeichnisse werden nicht verfolgt.
+    fn watch_tree(&mut self, bundle: &Path, dir: &Path) {
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let watch = Watch::Bundle { bundle: bundle.to_path_buf(), dir: dir.clone() };
+            if self.add_watch(&dir, BUNDLE_MASK, watch).is_err() {
+                continue;
+            }
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            pending.extend(
+                entries
+                    .flatten()
+                    .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+                    .map(|entry| entry.path()),
+            );
+        }
+    }
+
+    /// Wartet höchstens `timeout` (ohne: unbegrenzt) auf Änderungen und liefert
+    /// die Ereignisse aller Bundles, deren Wartezeit abgelaufen ist. Eine leere
+    /// Liste bedeutet, dass die Zeit ohne abgeschlossene Änderung verstrichen ist.
+    pub fn poll(&mut self, timeout: Option<Duration>) -> Result<Vec<BundleEvent>, BundleError> {
+        let deadline = timeout.map(|t| Instant::now() + t);
+        loop {
+            let events = self.settle(Instant::now());
+            if !events.is_empty() {
+                return Ok(events);
+            }
+
+            let now = Instant::now();
+            if deadline.is_some_and(|d| d <= now) {
+                return Ok(events);
+            }
+            let next_settle = self.pending.values().min().copied();
+            let wait = [deadline, next_settle]
+                .into_iter()
+                .flatten()
+                .min()
+                .map(|until| until.saturating_duration_since(now));
+
+            if self.wait_readable(wait)? {
+                self.read_events()?;
+            }
+        }
+    }
+
+    /// Dateideskriptor der inotify-Instanz, z.B. zur Einbindung in eine Event-Loop.
+    /// Ist er lesbar, liefert [`BundleWatcher::poll`] mit `Some(Duration::ZERO)`
+    /// die angefallenen Ereignisse, sobald deren Wartezeit abgelaufen ist.
+    pub fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+
+    fn wait_readable(&self, wait: Option<Duration>) -> Result<bool, BundleError> {
+        let mut pollfd = libc::pollfd { fd: self.fd.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+        let timeout_ms = match wait {
+            Some(wait) => wait.as_millis().min(i32::MAX as u128) as i32,
+            None => -1,
+        };
+        let ready = unsafe { libc::poll(&mut pollfd, 1, timeout_ms) };
+        if ready < 0 {
+            let err = std::io::Error::last_os_error();
+            if err.kind() == std::io::ErrorKind::Interrupted {
+                return Ok(false);
+            }
+            return Err(BundleError::IoError(err));
+        }
+        Ok(ready > 0)
+    }
+
+    /// Liest alle anstehenden inotify-Ereignisse und markiert betroffene Bundles.
+    fn read_events(&mut self) -> Result<(), BundleError> {
+        // inotify_event verlangt 4-Byte-Ausrichtung
+        let mut buffer = [0u32; 1024];
+        loop {
+            let len = unsafe {
+                libc::read(self.fd.as_raw_fd(), buffer.as_mut_ptr().cast(), std::mem::size_of_val(&buffer))
+            };
+            if len < 0 {
+                let err = std::io::Error::last_os_error();
+                return match err.kind() {
+                    std::io::ErrorKind::WouldBlock => Ok(()),
+                    std::io::ErrorKind::Interrupted => continue,
+                    _ => Err(BundleError::IoError(err)),
+                };
+            }
+            let bytes = unsafe { std::slice::from_raw_parts(buffer.as_ptr().cast::<u8>(), len as usize) };
+            self.handle_buffer(bytes);
+        }
+    }
+
+    fn handle_buffer(&mut self, bytes: &[u8]) {
+        let header = std::mem::size_of::<libc::inotify_event>();
+        let mut offset = 0;
+        while offset + header <= bytes.len() {
+            let event = unsafe { std::ptr::read_unaligned(bytes[offset..].as_ptr().cast::<libc::inotify_event>()) };
+            let name_bytes = &bytes[offset + header..offset + header + event.len as usize];
+            let name_end = name_bytes.iter().position(|&b| b == 0).unwrap_or(name_bytes.len());
+            let name = OsStr::from_bytes(&name_bytes[..name_end]);
+            offset += header + event.len as usize;
+
+            if event.mask & libc::IN_Q_OVERFLOW != 0 {
+                self.mark_everything();
+                continue;
+            }
+            if event.mask & libc::IN_IGNORED != 0 {
+                self.watches.remove(&event.wd);
+                continue;
+            }
+            let new_dir = event.mask & libc::IN_ISDIR != 0 && event.mask & (libc::IN_CREATE | libc::IN_MOVED_TO) != 0;
+            let target = match self.watches.get(&event.wd).cloned() {
+                Some(Watch::Root(root)) if !name.is_empty() => {
+                    let bundle = root.join(name);
+                    if new_dir {
+                        self.watch_bundle(&bundle);
+                    }
+                    bundle
+                }
+                Some(Watch::Bundle { bundle, dir }) => {
+                    // Neue Unterverzeichnisse sofort beobachten, damit auch Schreibvorgänge
+                    // darin die Wartezeit verlängern
+                    if new_dir {
+                        self.watch_tree(&bundle, &dir.join(name));
+                    }
+                    bundle
+                }
+                _ => continue,
+            };
+            self.mark(target);
+        }
+    }
+
+    /// Setzt die Wartezeit für `path` (neu).
+    fn mark(&mut self, path: PathBuf) {
+        self.pending.insert(path, Instant::now() + self.debounce);
+    }
+
+    /// Nach einem Überlauf der Ereignis-Warteschlange alles erneut prüfen.
+    fn mark_everything(&mut self) {
+        let mut paths: Vec<PathBuf> = self.known.keys().cloned().collect();
+        for root in &self.roots {
+            paths.extend(bundle_candidates(root));
+        }
+        for path in paths {
+            // Verzeichnisse, deren Anlage im Überlauf verloren ging, nachtragen
+            if path.is_dir() {
+                self.watch_bundle(&path);
+            }
+            self.mark(path);
+        }
+    }
+
+    /// Prüft alle Bundles, deren Wartezeit bis `now` abgelaufen ist.
+    fn settle(&mut self, now: Instant) -> Vec<BundleEvent> {
+        let due: Vec<PathBuf> = self
+            .pending
+            .iter()
+            .filter(|(_, at)| **at <= now)
+            .map(|(path, _)| path.clone())
+            .collect();
+
+        let mut events = Vec::new();
+        for path in due {
+            self.pending.remove(&path);
+            let current = valid_bundle(&path);
+            match (self.known.remove(&path), current) {
+                (None, Some(new)) => {
+                    events.push(BundleEvent::BundleAdded { identifier: new.identifier.clone(), path: path.clone() });
+                    self.known.insert(path, new);
+                }
+                (Some(old), None) => {
+                    events.push(BundleEvent::BundleRemoved { identifier: old.identifier, path });
+                }
+                (Some(old), Some(new)) => {
+                    if old.identifier != new.identifier {
+                        events.push(BundleEvent::BundleRemoved { identifier: old.identifier, path: path.clone() });
+                        events.push(BundleEvent::BundleAdded { identifier: new.identifier.clone(), path: path.clone() });
+                    } else if old.stamp != new.stamp {
+                        events.push(BundleEvent::InfoChanged { identifier: new.identifier.clone(), path: path.clone() });
+                    }
+                    self.known.insert(path, new);
+                }
+                (None, None) => {
+                    // Noch unvollständig; weitere Aktivität markiert das Bundle erneut
+                    if path.is_dir() {
+                        self.watch_bundle(&path);
+                    }
+                }
+            }
+        }
+        events
+    }
+}
+
+/// Prüft, ob `path` ein gültiges Bundle mit lesbarer Info.json ist.
+fn valid_bundle(path: &Path) -> Option<KnownBundle> {
+    BundleKind::detect(path)?;
+    let info = load_bundle_info_file(path).ok()?;
+    Some(KnownBundle { identifier: info.identifier, stamp: FileStamp::of_info_file(path) })
+}
@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::Bundle;
+
+/// Prüft, ob `path` relativ ist und nur aus normalen Komponenten besteht,
+/// also weder mit `..` noch absolut aus `Resources/` herausführt.
+fn is_contained(path: &str) -> bool {
+    !path.is_empty() && Path::new(path).components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+impl Bundle {
+    /// Pfad zum Ressourcenverzeichnis `Content/Resources`.
+    pub fn resources_path(&self) -> PathBuf {
+        self.content_path().join("Resources")
+    }
+
+    /// Verzeichnisse, die bei der Ressourcensuche in dieser Reihenfolge durchsucht werden:
+    /// zuerst `Resources/<sprache>.lproj/<subdirectory>` für jede Sprache aus
+    /// [`Bundle::preferred_localizations`],
+    /// danach `Resources/<subdirectory>`.
+    /// Führt `subdirectory` aus `Resources/` heraus, ist die Liste leer.
+    pub fn resource_search_paths(&self, subdirectory: Option<&str>) -> Vec<PathBuf> {
+        if subdirectory.is_some_and(|sub| !is_contained(sub)) {
+            return Vec::new();
+        }
+        let resources = self.resources_path();
+        let with_subdirectory = |dir: PathBuf| match subdirectory {
+            Some(sub) => dir.join(sub),
+            None => dir,
+        };
+
+        let mut paths: Vec<PathBuf> = self
+            .preferred_localizations()
+            .into_iter()
+            .map(|lang| with_subdirectory(resources.join(format!("{}.lproj", lang))))
+            .collect();
+        paths.push(with_subdirectory(resources));
+        paths
+    }
+
+    /// Sucht eine Ressource `name.extension` im Bundle.
+    /// Lokalisierte Varianten haben Vorrang vor der Basisversion.
+    /// Ohne `extension` muss `name` den vollständigen Dateinamen enthalten.
+    /// Namen mit `..` oder absolute Pfade werden nicht aufgelöst.
+    pub fn path_for_resource(
+        &self,
+        name: &str,
+        extension: Option<&str>,
+        subdirectory: Option<&str>,
+    ) -> Option<PathBuf> {
+        let file_name = match extension {
+            Some(ext) if !ext.is_empty() => format!("{}.{}", name, ext),
+            _ => name.to_string(),
+        };
+        if !is_contained(&file_name) {
+            return None;
+        }
+
+        self.resource_search_paths(subdirectory)
+            .into_iter()
+            .map(|dir| dir.join(&file_name))
+            .find(|path| path.is_file())
+    }
+
+    /// Liefert alle Ressourcen mit der Endung `extension`.
+    /// Eine lokalisierte Datei verdeckt die gleichnamige Basisdatei; innerhalb
+    /// eines Verzeichnisses sind die Ergebnisse nach Dateinamen sortiert.
+    pub fn paths_for_resources_of_type(&self, extension: &str, subdirectory: Option<&str>) -> Vec<PathBuf> {
+        let mut seen = HashSet::new();
+        let mut found = Vec::new();
+
+        for dir in self.resource_search_paths(subdirectory) {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == extension))
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                if let Some(file_name) = path.file_name()
+                    && seen.insert(file_name.to_os_string())
+                {
+                    found.push(path);
+                }
+            }
+        }
+        found
+    }
+}
@@ -0,0 +1,336 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::executables::stays_inside;
+use crate::launch::{ENV_BUNDLE_CACHE_DIR, ENV_BUNDLE_CONFIG_DIR, ENV_BUNDLE_DATA_DIR};
+use crate::launch_config::{check_template, expand, placeholders, sample_expansion};
+use crate::{
+    Bundle, BundleError, BundleKind, BundleRegistry, BundleValidationError, BundleValidationResult, HealthCheck, ServiceConfig,
+    ServiceSocket,
+};
+
+/// Zeitlimit einer Gesundheitsprüfung ohne `timeout_seconds`.
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+impl ServiceConfig {
+    /// Prüft den Abschnitt für sich: Zeitangaben, Gesundheitsprüfung und
+    /// Abhängigkeiten. Ob die Abhängigkeiten installiert sind und keinen Zyklus
+    /// bilden, prüft [`BundleRegistry::validate_services`].
+    pub fn validate(&self, identifier: &str) -> Result<(), BundleValidationError> {
+        let invalid = BundleValidationError::InvalidFormat;
+        if self.start_timeout_seconds == Some(0) {
+            return Err(invalid("service.start_timeout_seconds must be greater than 0".to_string()));
+        }
+        if let Some(check) = &self.health_check {
+            check.validate()?;
+        }
+        let mut names = HashSet::new();
+        for socket in &self.sockets {
+            socket.validate()?;
+            if !names.insert(socket.name.as_str()) {
+                return Err(invalid(format!("service.sockets: duplicate name '{}'", socket.name)));
+            }
+        }
+        for (field, dependencies) in [("after", &self.after), ("requires", &self.requires)] {
+            for dependency in dependencies {
+                if dependency.is_empty() {
+                    return Err(invalid(format!("service.{}: empty identifier", field)));
+                }
+                if dependency == identifier {
+                    return Err(invalid(format!("service.{}: service depends on itself", field)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// [`ServiceConfig::validate`] für Aufrufer, die den Abschnitt verwenden,
+    /// etwa beim Anlegen von Sockets oder Units.
+    pub(crate) fn check(&self, identifier: &str) -> Result<(), BundleError> {
+        self.validate(identifier).map_err(|e| match e {
+            BundleValidationError::MissingField(message)
+            | BundleValidationError::InvalidFormat(message)
+            | BundleValidationError::ConfigLoadError(message) => BundleError::InvalidFormat(message),
+        })
+    }
+
+    /// Alle Dienste, die vor diesem gestartet werden müssen.
+    pub fn dependencies(&self) -> impl Iterator<Item = &str> {
+        self.after.iter().chain(&self.requires).map(String::as_str)
+    }
+}
+
+/// Platzhalter, die in `service.sockets[].path` nicht erlaubt sind: systemd legt
+/// diese Verzeichnisse unter `%S`, `%C` und `%E` an, der eigene Starter unter
+/// den XDG-Verzeichnissen des Benutzers. Ein Socket hätte so je nach Start
+/// einen anderen Pfad.
+pub const SOCKET_PATH_FORBIDDEN_PLACEHOLDERS: &[&str] = &[ENV_BUNDLE_DATA_DIR, ENV_BUNDLE_CACHE_DIR, ENV_BUNDLE_CONFIG_DIR];
+
+/// Prüft den Pfad eines Unix-Sockets: bekannte, erlaubte Platzhalter und absolut nach der Ersetzung.
+pub(crate) fn check_socket_path(path: &str) -> Result<(), String> {
+    let expanded = sample_expansion(path)?;
+    if let Some(name) = placeholders(path)?
+        .into_iter()
+        .find(|name| SOCKET_PATH_FORBIDDEN_PLACEHOLDERS.contains(name))
+    {
+        return Err(format!("placeholder '${{{}}}' is not allowed in socket paths", name));
+    }
+    if !expanded.starts_with('/') {
+        return Err("path must be absolute".to_string());
+    }
+    Ok(())
+}
+
+impl ServiceSocket {
+    fn validate(&self) -> Result<(), BundleValidationError> {
+        let invalid = |message: &str| {
+            BundleValidationError::InvalidFormat(format!("service.sockets '{}': {}", self.name, message))
+        };
+        if self.name.is_empty()
+            || self.name.len() > 255
+            || !self.name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+        {
+            return Err(invalid("name may only contain letters, digits, '_', '.' and '-'"));
+        }
+        match (&self.path, self.port) {
+            (Some(path), None) => {
+                check_socket_path(path).map_err(|e| invalid(&e))?;
+                if self.address.is_some() {
+                    return Err(invalid("address is only allowed with port"));
+                }
+            }
+            (None, Some(0)) => return Err(invalid("port must not be 0")),
+            (None, Some(_)) => {}
+            _ => return Err(invalid("exactly one of path and port is required")),
+        }
+        Ok(())
+    }
+}
+
+impl HealthCheck {
+    fn validate(&self) -> Result<(), BundleValidationError> {
+        let invalid = BundleValidationError::InvalidFormat;
+        match (self.command.is_empty(), &self.socket) {
+            (true, None) => {
+                return Err(invalid("service.health_check: either command or socket is required".to_string()));
+            }
+            (false, Some(_)) => {
+                return Err(invalid("service.health_check: command and socket are mutually exclusive".to_string()));
+            }
+            _ => {}
+        }
+        if self.command.first().is_some_and(|program| program.is_empty()) {
+            return Err(invalid("service.health_check.command: empty program".to_string()));
+        }
+        for part in self.command.iter().chain(&self.socket) {
+            check_template(part).map_err(|e| invalid(format!("service.health_check: {}", e)))?;
+        }
+        if let Some(socket) = &self.socket {
+            let expanded = sample_expansion(socket).map_err(|e| invalid(format!("service.health_check: {}", e)))?;
+            if !expanded.starts_with('/')
+                && expanded.rsplit_once(':').is_none_or(|(_, port)| port.parse::<u16>().is_err())
+            {
+                return Err(invalid(format!(
+                    "service.health_check.socket: '{}' is neither an absolute path nor host:port",
+                    socket
+                )));
+            }
+        }
+        if self.timeout_seconds == Some(0) {
+            return Err(invalid("service.health_check.timeout_seconds must be greater than 0".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Zeitlimit einer einzelnen Prüfung.
+    pub fn timeout(&self) -> Duration {
+        self.timeout_seconds.map(Duration::from_secs).unwrap_or(DEFAULT_HEALTH_CHECK_TIMEOUT)
+    }
+}
+
+impl Bundle {
+    /// Führt die Gesundheitsprüfung aus dem Abschnitt `service` einmal aus.
+    ///
+    /// Ein Befehl gilt als gesund, wenn er innerhalb des Zeitlimits mit Status 0
+    /// endet, sonst wird er beendet. Ein Socket gilt als gesund, wenn er eine
+    /// Verbindung annimmt. Ohne Gesundheitsprüfung ist das Ergebnis `NotFound`,
+    /// ein Programm außerhalb von `Content/` ergibt `InvalidFormat`. Wie oft
+    /// geprüft wird, entscheidet der Aufrufer.
+    pub fn check_health(&self) -> Result<bool, BundleError> {
+        let check = self
+            .info()?
+            .service
+            .as_ref()
+            .and_then(|service| service.health_check.as_ref())
+            .ok_or_else(|| BundleError::NotFound(format!("'{}' declares no health check", self.path().display())))?;
+        let bundle_env = self.command_for(self.executable_path()?)?.env;
+
+        if let Some(socket) = &check.socket {
+            let address = expand(socket, &bundle_env)?;
+            return Ok(if Path::new(&address).is_absolute() {
+                UnixStream::connect(&address).is_ok()
+            } else {
+                let address = address.to_string_lossy();
+                address
+                    .to_socket_addrs()
+                    .map(|mut addrs| addrs.any(|addr| TcpStream::connect_timeout(&addr, check.timeout()).is_ok()))
+                    .unwrap_or(false)
+            });
+        }
+
+        let (program, args) = check
+            .command
+            .split_first()
+            .ok_or_else(|| BundleError::InvalidFormat("service.health_check has no command".to_string()))?;
+        // Platzhalter wie `${BUNDLE_CONTENT_PATH}` ergeben absolute Pfade innerhalb von Content/
+        let program = PathBuf::from(expand(program, &bundle_env)?);
+        let content = self.content_path();
+        if !stays_inside(program.strip_prefix(&content).unwrap_or(&program)) {
+            return Err(BundleError::InvalidFormat(format!(
+                "service.health_check.command points outside Content/: '{}'",
+                program.display()
+            )));
+        }
+        let mut command = self.command_for(content.join(program))?;
+        command.args = args.iter().map(|arg| expand(arg, &bundle_env)).collect::<Result<Vec<OsString>, _>>()?;
+
+        let mut process = command.spawn()?;
+        let deadline = Instant::now() + check.timeout();
+        loop {
+            if let Some(status) = process.try_wait()? {
+                return Ok(status.success());
+            }
+            if Instant::now() >= deadline {
+                process.kill(libc::SIGKILL)?;
+                process.wait()?;
+                return Ok(false);
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+/// Sucht Zyklen im Abhängigkeitsgraphen; jeder Zyklus einmal, beginnend beim kleinsten Bezeichner.
+fn dependency_cycles(graph: &HashMap<&str, Vec<&str>>) -> Vec<Vec<String>> {
+    fn visit<'a>(
+        node: &'a str,
+        graph: &HashMap<&'a str, Vec<&'a str>>,
+        stack: &mut Vec<&'a str>,
+        done: &mut HashSet<&'a str>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        if let Some(at) = stack.iter().position(|&n| n == node) {
+            let mut cycle: Vec<String> = stack[at..].iter().map(|n| n.to_string()).collect();
+            let start = (0..cycle.len()).min_by_key(|&i| &cycle[i]).unwrap_or(0);
+            cycle.rotate_left(start);
+            if !cycles.contains(&cycle) {
+                cycles.push(cycle);
+            }
+            return;
+        }
+        if done.contains(node) {
+            return;
+        }
+        stack.push(node);
+        for &next in graph.get(node).into_iter().flatten() {
+            visit(next, graph, stack, done, cycles);
+        }
+        stack.pop();
+        done.insert(node);
+    }
+
+    let mut nodes: Vec<&str> = graph.keys().copied().collect();
+    nodes.sort();
+    let mut cycles = Vec::new();
+    let mut done = HashSet::new();
+    for node in nodes {
+        visit(node, graph, &mut Vec::new(), &mut done, &mut cycles);
+    }
+    cycles
+}
+
+impl BundleRegistry {
+    /// Prüft die `service`-Abschnitte aller wirksamen Bundles: jeden für sich,
+    /// die Abhängigkeiten auf installierte Service-Bundles und das
+    /// Abhängigkeitsgeflecht auf Zyklen.
+    ///
+    /// Fehlende Ziele von `requires` sind Fehler, fehlende Ziele von `after`
+    /// nur Warnungen.
+    pub fn validate_services(&self) -> BundleValidationResult {
+        let mut problems = Vec::new();
+        let mut warnings = Vec::new();
+        let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        let mut entries: Vec<_> = self.bundles().filter(|entry| entry.info.service.is_some()).collect();
+        entries.sort_by(|a, b| a.identifier().cmp(b.identifier()));
+        for entry in entries {
+            let identifier = entry.identifier();
+            let Some(service) = &entry.info.service else {
+                continue;
+            };
+            if entry.kind != BundleKind::Service {
+                warnings.push(format!("'{}' is not a service bundle; its service section is ignored", identifier));
+                continue;
+            }
+            if let Err(e) = service.validate(identifier) {
+                problems.push(format!("'{}': {}", identifier, e));
+            }
+            for (dependencies, required) in [(&service.requires, true), (&service.after, false)] {
+                for dependency in dependencies {
+                    let message = match self.get(dependency) {
+                        Some(target) if target.kind == BundleKind::Service => continue,
+                        Some(_) => format!("'{}' depends on '{}', which is not a service", identifier, dependency),
+                        None => format!("'{}' depends on '{}', which is not installed", identifier, dependency),
+                    };
+                    if required {
+                        problems.push(message);
+                    } else {
+                        warnings.push(message);
+                    }
+                }
+            }
+            graph.insert(identifier, service.dependencies().collect());
+        }
+
+        for cycle in dependency_cycles(&graph) {
+            problems.push(format!("dependency cycle: {} -> {}", cycle.join(" -> "), cycle[0]));
+        }
+
+        BundleValidationResult {
+            is_valid: problems.is_empty(),
+            message: if problems.is_empty() {
+                "Dienste sind gültig".to_string()
+            } else {
+                problems.join("; ")
+            },
+            warnings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn socket_check(socket: &str) -> Result<(), BundleValidationError> {
+        HealthCheck { socket: Some(socket.to_string()), ..HealthCheck::default() }.validate()
+    }
+
+    #[test]
+    fn health_check_socket_is_checked_after_expansion() {
+        assert!(socket_check("/run/echo.sock").is_ok());
+        assert!(socket_check("127.0.0.1:7").is_ok());
+        assert!(socket_check("${BUNDLE_DATA_DIR}/health.sock").is_ok());
+        assert!(socket_check("${BUNDLE_IDENTIFIER}.local:80").is_ok());
+
+        assert!(socket_check("${BUNDLE_NOPE}/health.sock").is_err());
+        assert!(socket_check("${BUNDLE_DATA_DIR").is_err());
+        assert!(socket_check("${BUNDLE_IDENTIFIER}").is_err());
+        assert!(socket_check("relative${BUNDLE_DATA_DIR}").is_err());
+        assert!(socket_check("localhost:http").is_err());
+    }
+}
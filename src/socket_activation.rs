@@ -0,0 +1,249 @@
+use std::ffi::OsString;
+use std::fs;
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, TcpListener};
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::launch_config::expand;
+use crate::service_config::check_socket_path;
+use crate::{Bundle, BundleError, ServiceSocket};
+
+/// Erster übergebener Deskriptor im LISTEN_FDS-Protokoll.
+pub const SD_LISTEN_FDS_START: RawFd = 3;
+pub const ENV_LISTEN_FDS: &str = "LISTEN_FDS";
+pub const ENV_LISTEN_PID: &str = "LISTEN_PID";
+pub const ENV_LISTEN_FDNAMES: &str = "LISTEN_FDNAMES";
+
+/// Name eines Deskriptors ohne `LISTEN_FDNAMES`, wie bei systemd.
+const UNKNOWN_NAME: &str = "unknown";
+
+/// Die Deskriptoren dürfen nur einmal übernommen werden.
+static LISTEN_FDS_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Per Socket-Aktivierung geerbte Deskriptoren mit ihren Namen.
+#[derive(Debug, Default)]
+pub struct ListenSockets {
+    sockets: Vec<(String, OwnedFd)>,
+}
+
+impl ListenSockets {
+    pub fn len(&self) -> usize {
+        self.sockets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sockets.is_empty()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.sockets.iter().map(|(name, _)| name.as_str())
+    }
+
+    pub fn get(&self, name: &str) -> Option<BorrowedFd<'_>> {
+        self.sockets.iter().find(|(n, _)| n == name).map(|(_, fd)| fd.as_fd())
+    }
+
+    /// Entnimmt den ersten Deskriptor mit dem Namen `name`.
+    pub fn take(&mut self, name: &str) -> Option<OwnedFd> {
+        let index = self.sockets.iter().position(|(n, _)| n == name)?;
+        Some(self.sockets.remove(index).1)
+    }
+
+    pub fn take_unix_listener(&mut self, name: &str) -> Option<UnixListener> {
+        self.take(name).map(UnixListener::from)
+    }
+
+    pub fn take_tcp_listener(&mut self, name: &str) -> Option<TcpListener> {
+        self.take(name).map(TcpListener::from)
+    }
+
+    pub fn into_vec(self) -> Vec<(String, OwnedFd)> {
+        self.sockets
+    }
+}
+
+/// Übernimmt die nach dem LISTEN_FDS-Protokoll geerbten Deskriptoren.
+///
+/// Sie gelten nur, wenn `LISTEN_PID` dieser Prozess ist. Die Deskriptoren
+/// erhalten `FD_CLOEXEC`. Nur der erste Aufruf liefert Deskriptoren, alle
+/// weiteren eine leere Liste. Die Variablen bleiben gesetzt; gestartete
+/// Kindprozesse ignorieren sie wegen der abweichenden PID.
+pub fn listen_fds() -> Result<ListenSockets, BundleError> {
+    if LISTEN_FDS_TAKEN.swap(true, Ordering::SeqCst) {
+        return Ok(ListenSockets::default());
+    }
+    let var = |key: &str| std::env::var(key).ok();
+    let for_us = var(ENV_LISTEN_PID)
+        .and_then(|pid| pid.parse::<u32>().ok())
+        .is_some_and(|pid| pid == std::process::id());
+    if !for_us {
+        return Ok(ListenSockets::default());
+    }
+    let count: RawFd = var(ENV_LISTEN_FDS)
+        .ok_or_else(|| BundleError::InvalidFormat(format!("{} is not set", ENV_LISTEN_FDS)))?
+        .parse()
+        .map_err(|_| BundleError::InvalidFormat(format!("{} is not a number", ENV_LISTEN_FDS)))?;
+    let end = SD_LISTEN_FDS_START
+        .checked_add(count)
+        .filter(|_| count >= 0)
+        .ok_or_else(|| BundleError::InvalidFormat(format!("{} is out of range: {}", ENV_LISTEN_FDS, count)))?;
+    // Namen sind positionsgebunden; leere Einträge heißen wie bei systemd "unknown"
+    let names = var(ENV_LISTEN_FDNAMES).unwrap_or_default();
+    let mut names = names.split(':');
+
+    let mut sockets = ListenSockets::default();
+    for fd in SD_LISTEN_FDS_START..end {
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } < 0 {
+            return Err(BundleError::IoError(std::io::Error::last_os_error()));
+        }
+        let name = names.next().filter(|name| !name.is_empty()).unwrap_or(UNKNOWN_NAME).to_string();
+        sockets.sockets.push((name, unsafe { OwnedFd::from_raw_fd(fd) }));
+    }
+    Ok(sockets)
+}
+
+/// Legt den Socket `socket` an; `env` liefert die Werte der Platzhalter.
+fn bind_socket(socket: &ServiceSocket, env: &[(OsString, OsString)]) -> Result<OwnedFd, BundleError> {
+    if let Some(path) = &socket.path {
+        check_socket_path(path)
+            .map_err(|e| BundleError::InvalidFormat(format!("socket '{}': {}", socket.name, e)))?;
+        let path = expand(path, env)?;
+        let path = Path::new(&path);
+        if fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_socket()) {
+            remove_stale_socket(&socket.name, path)?;
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        return Ok(UnixListener::bind(path)?.into());
+    }
+    let port = socket.port.ok_or_else(|| {
+        BundleError::InvalidFormat(format!("socket '{}' declares neither path nor port", socket.name))
+    })?;
+    let (listener, address) = match socket.address.as_deref() {
+        Some(address) if address.contains(':') => (TcpListener::bind((address, port)), format!("[{}]:{}", address, port)),
+        Some(address) => (TcpListener::bind((address, port)), format!("{}:{}", address, port)),
+        None => (bind_any_address(port), format!("[::]:{}", port)),
+    };
+    match listener {
+        Ok(listener) => Ok(listener.into()),
+        Err(e) if e.kind() == ErrorKind::AddrInUse => Err(address_in_use(&socket.name, &address)),
+        Err(e) => Err(BundleError::IoError(e)),
+    }
+}
+
+/// Lauscht wie systemd bei `ListenStream=<port>` auf allen Adressen: auf `[::]`
+/// mit `IPV6_V6ONLY=0` für IPv6 und IPv4, ohne IPv6-Unterstützung auf `0.0.0.0`.
+fn bind_any_address(port: u16) -> std::io::Result<TcpListener> {
+    let raw = unsafe { libc::socket(libc::AF_INET6, libc::SOCK_STREAM | libc::SOCK_CLOEXEC, 0) };
+    if raw < 0 {
+        let err = std::io::Error::last_os_error();
+        if err.raw_os_error() == Some(libc::EAFNOSUPPORT) {
+            return TcpListener::bind((Ipv4Addr::UNSPECIFIED, port));
+        }
+        return Err(err);
+    }
+    let fd = unsafe { OwnedFd::from_raw_fd(raw) };
+
+    for (level, option, value) in [(libc::IPPROTO_IPV6, libc::IPV6_V6ONLY, 0), (libc::SOL_SOCKET, libc::SO_REUSEADDR, 1)] {
+        let value: libc::c_int = value;
+        let result = unsafe {
+            libc::setsockopt(
+                fd.as_raw_fd(),
+                level,
+                option,
+                (&value as *const libc::c_int).cast(),
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t,
+            )
+        };
+        if result < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+
+    // Null-initialisiert ist sin6_addr die Wildcard-Adresse `::`
+    let mut address: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+    address.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+    address.sin6_port = port.to_be();
+    let bound = unsafe {
+        libc::bind(
+            fd.as_raw_fd(),
+            (&address as *const libc::sockaddr_in6).cast(),
+            std::mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+        )
+    };
+    if bound < 0 || unsafe { libc::listen(fd.as_raw_fd(), libc::SOMAXCONN) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(TcpListener::from(fd))
+}
+
+/// Entfernt die Socket-Datei eines früheren Laufs. Nimmt dort noch jemand
+/// Verbindungen an, läuft der Dienst bereits und die Datei bleibt erhalten.
+fn remove_stale_socket(name: &str, path: &Path) -> Result<(), BundleError> {
+    match UnixStream::connect(path) {
+        Ok(_) => Err(address_in_use(name, &path.display().to_string())),
+        Err(e) if e.kind() == ErrorKind::ConnectionRefused => Ok(fs::remove_file(path)?),
+        Err(e) => Err(BundleError::IoError(std::io::Error::new(
+            e.kind(),
+            format!("socket '{}': cannot check existing socket '{}': {}", name, path.display(), e),
+        ))),
+    }
+}
+
+fn address_in_use(name: &str, address: &str) -> BundleError {
+    BundleError::IoError(std::io::Error::new(
+        ErrorKind::AddrInUse,
+        format!("socket '{}': {} is already in use, is the service already running?", name, address),
+    ))
+}
+
+impl Bundle {
+    /// Deklarierte Sockets aus `service.sockets`.
+    pub fn service_sockets(&self) -> Result<&[ServiceSocket], BundleError> {
+        Ok(self.info()?.service.as_ref().map(|service| service.sockets.as_slice()).unwrap_or(&[]))
+    }
+
+    /// Legt alle deklarierten Sockets an, in Deklarationsreihenfolge.
+    pub(crate) fn bind_service_sockets(&self) -> Result<Vec<(String, OwnedFd)>, BundleError> {
+        if let Some(service) = &self.info()?.service {
+            service.check(self.identifier()?)?;
+        }
+        let env = self.command_for(self.executable_path()?)?.env;
+        self.service_sockets()?
+            .iter()
+            .map(|socket| Ok((socket.name.clone(), bind_socket(socket, &env)?)))
+            .collect()
+    }
+
+    /// Übernimmt die geerbten Sockets dieses Dienstes (siehe [`listen_fds`])
+    /// und ordnet sie den deklarierten Namen zu.
+    ///
+    /// Ohne `LISTEN_FDNAMES` werden die Deskriptoren in Deklarationsreihenfolge
+    /// zugeordnet. Nicht deklarierte Deskriptoren werden geschlossen.
+    pub fn listen_sockets(&self) -> Result<ListenSockets, BundleError> {
+        let declared = self.service_sockets()?;
+        let inherited = listen_fds()?.into_vec();
+        let unnamed = inherited.iter().all(|(name, _)| name == UNKNOWN_NAME);
+
+        let mut sockets = ListenSockets::default();
+        for (index, (name, fd)) in inherited.into_iter().enumerate() {
+            let name = if unnamed {
+                match declared.get(index) {
+                    Some(socket) => socket.name.clone(),
+                    None => continue,
+                }
+            } else {
+                name
+            };
+            if declared.iter().any(|socket| socket.name == name) {
+                sockets.sockets.push((name, fd));
+            }
+        }
+        Ok(sockets)
+    }
+}
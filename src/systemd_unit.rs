@@ -0,0 +1,451 @@
+use std::ffi::OsString;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::launch::{
+    ENV_BUNDLE_CACHE_DIR, ENV_BUNDLE_CONFIG_DIR, ENV_BUNDLE_CONTENT_PATH, ENV_BUNDLE_DATA_DIR,
+    ENV_BUNDLE_EXECUTABLE, ENV_BUNDLE_IDENTIFIER, ENV_BUNDLE_PATH,
+};
+use crate::atomic_file::write_atomically;
+use crate::executables::stays_inside;
+use crate::launch_config::expand;
+use crate::service_config::check_socket_path;
+use crate::{xdg, Bundle, BundleError, BundleInfoConfigFile, BundleKind, EntitlementType, RestartPolicy};
+
+/// Ob die Unit vom Benutzer- oder vom System-Manager ausgeführt wird.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemdScope {
+    User,
+    System,
+}
+
+impl SystemdScope {
+    /// Verzeichnis für selbst erzeugte Units:
+    /// `$XDG_CONFIG_HOME/systemd/user` bzw. `/etc/systemd/system`.
+    pub fn unit_directory(self) -> Option<PathBuf> {
+        match self {
+            SystemdScope::User => xdg::config_home().map(|dir| dir.join("systemd/user")),
+            SystemdScope::System => Some(PathBuf::from("/etc/systemd/system")),
+        }
+    }
+
+    fn wanted_by(self) -> &'static str {
+        match self {
+            SystemdScope::User => "default.target",
+            SystemdScope::System => "multi-user.target",
+        }
+    }
+}
+
+/// Verdoppelt `%`, damit systemd keine Specifier erkennt.
+fn escape_specifiers(value: &str) -> String {
+    value.replace('%', "%%")
+}
+
+/// Quotet ein Argument für `ExecStart=`; `$` wird verdoppelt, damit systemd
+/// keine Variablen ersetzt.
+fn quote_exec_argument(value: &str) -> String {
+    let escaped = value.replace('$', "$$");
+    if !escaped.is_empty() && !escaped.contains(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';')) {
+        return escaped;
+    }
+    format!("\"{}\"", escaped.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn quote_environment(key: &str, value: &str) -> String {
+    format!("\"{}={}\"", key, value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Unit-Dateien sind zeilenbasiert: ein Zeilenumbruch in einem Wert aus der
+/// Info.json würde weitere Direktiven einschleusen. Steuerzeichen sind daher
+/// in keinem Wert erlaubt.
+fn check_unit_value(what: &str, value: &str) -> Result<(), BundleError> {
+    if value.contains(char::is_control) {
+        return Err(BundleError::InvalidFormat(format!(
+            "{} contains control characters: {:?}",
+            what, value
+        )));
+    }
+    Ok(())
+}
+
+fn to_unit_string(value: OsString) -> Result<String, BundleError> {
+    value
+        .into_string()
+        .map_err(|value| BundleError::InvalidFormat(format!("'{}' is not valid UTF-8", value.to_string_lossy())))
+}
+
+/// Ziele, nach denen ein Dienst mit dem Hintergrundmodus `mode` startet.
+/// Unbekannte Modi werden ignoriert.
+fn background_mode_dependencies(mode: &str) -> &'static [&'static str] {
+    match mode {
+        "network" | "fetch" => &["network-online.target"],
+        "bluetooth" => &["bluetooth.target"],
+        "audio" => &["sound.target"],
+        _ => &[],
+    }
+}
+
+/// Geräteklassen, die ein Entitlement freigibt (`DeviceAllow=`).
+fn entitlement_devices(entitlement: &EntitlementType) -> &'static [&'static str] {
+    use EntitlementType::*;
+    match entitlement {
+        Camera | PiCameraModule => &["char-video4linux"],
+        Microphone => &["char-alsa"],
+        Usb | HardwareWalletAccess | HardwareWalletRead | HardwareWalletSign => &["char-usb_device", "char-hidraw"],
+        GpioAccess => &["char-gpiochip"],
+        I2cAccess => &["char-i2c"],
+        SpiAccess => &["char-spi"],
+        UartAccess => &["char-ttyS", "char-ttyAMA", "char-ttyUSB"],
+        PwmAccess => &["char-pwm"],
+        DisplayAccess => &["char-drm"],
+        VirtualMachine => &["/dev/kvm"],
+        VirtualFileSystem => &["/dev/fuse"],
+        _ => &[],
+    }
+}
+
+/// Adressfamilien, die ein Entitlement freigibt (`RestrictAddressFamilies=`).
+fn entitlement_address_families(entitlement: &EntitlementType) -> &'static [&'static str] {
+    use EntitlementType::*;
+    match entitlement {
+        Network | P2PNetwork | ChainSync | MempoolAccess | LightningChannels | LightningPay | LightningInfo
+        | RemoteSupport | SelfUpdate | EnterpriseSSO => &["AF_INET", "AF_INET6"],
+        Firewall | NetworkPolicyControl => &["AF_INET", "AF_INET6", "AF_NETLINK"],
+        VpnHost | VpnClient | EnterpriseVpn => &["AF_INET", "AF_INET6", "AF_NETLINK"],
+        Bluetooth => &["AF_BLUETOOTH"],
+        CanBusAccess => &["AF_CAN"],
+        _ => &[],
+    }
+}
+
+/// Name der Unit für den Bezeichner `identifier`, z.B. `org.example.echo.service`.
+fn unit_name(identifier: &str) -> Result<String, BundleError> {
+    if identifier.is_empty()
+        || !identifier.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, ':' | '_' | '.' | '-'))
+    {
+        return Err(BundleError::InvalidFormat(format!(
+            "identifier '{}' cannot be used as a systemd unit name",
+            identifier
+        )));
+    }
+    Ok(format!("{}.service", identifier))
+}
+
+/// Pfade des Bundles für Unit-Dateien, mit verdoppelten `%`.
+struct UnitPaths {
+    bundle: String,
+    content: String,
+    executable: String,
+    /// Werte der Platzhalter; die Datenverzeichnisse als systemd-Specifier.
+    bundle_env: Vec<(OsString, OsString)>,
+}
+
+fn unit_paths(info: &BundleInfoConfigFile, bundle_path: &Path) -> Result<UnitPaths, BundleError> {
+    unit_name(&info.identifier)?;
+    let identifier = &info.identifier;
+    let bundle_path = to_unit_string(bundle_path.as_os_str().to_owned())?;
+    check_unit_value("bundle path", &bundle_path)?;
+    check_unit_value("name", &info.name)?;
+    check_unit_value("entry_point", &info.entry_point)?;
+    // Socket-Namen landen in Unit-Namen und damit in Dateinamen
+    if let Some(service) = &info.service {
+        service.check(identifier)?;
+    }
+    if !stays_inside(Path::new(&info.entry_point)) {
+        return Err(BundleError::InvalidFormat(format!(
+            "entry_point points outside Content/: '{}'",
+            info.entry_point
+        )));
+    }
+    let bundle = escape_specifiers(&bundle_path);
+    let content = format!("{}/Content", bundle);
+    let executable = format!("{}/{}", content, escape_specifiers(&info.entry_point));
+
+    let bundle_env = [
+        (ENV_BUNDLE_IDENTIFIER, escape_specifiers(identifier)),
+        (ENV_BUNDLE_PATH, bundle.clone()),
+        (ENV_BUNDLE_CONTENT_PATH, content.clone()),
+        (ENV_BUNDLE_EXECUTABLE, executable.clone()),
+        (ENV_BUNDLE_DATA_DIR, format!("%S/{}", identifier)),
+        (ENV_BUNDLE_CACHE_DIR, format!("%C/{}", identifier)),
+        (ENV_BUNDLE_CONFIG_DIR, format!("%E/{}", identifier)),
+    ]
+    .into_iter()
+    .map(|(key, value)| (OsString::from(key), OsString::from(value)))
+    .collect();
+    Ok(UnitPaths { bundle, content, executable, bundle_env })
+}
+
+/// Name der Socket-Unit für den Socket `name` des Dienstes `identifier`.
+fn socket_unit_name(identifier: &str, name: &str) -> String {
+    format!("{}-{}.socket", identifier, name)
+}
+
+/// Erzeugt den Text einer systemd-Service-Unit für das Bundle unter `bundle_path`.
+///
+/// `ExecStart=` startet `entry_point` mit `launch.arguments`, die Umgebung
+/// enthält die `BUNDLE_*`-Variablen und `launch.environment`. Hintergrundmodi
+/// aus `app_services.background_modes` werden bei System-Units zu Abhängigkeiten
+/// (`network` → `network-online.target` usw.), ebenso `service.after` und
+/// `service.requires`. Neustart und Startzeitlimit kommen aus dem Abschnitt
+/// `service`; ohne ihn wird bei Fehlern neu gestartet. Die Daten-, Cache-
+/// und Konfigurationsverzeichnisse verwaltet systemd (`StateDirectory=` usw.);
+/// `BUNDLE_DATA_DIR` zeigt daher auf `%S/<identifier>`. Mit `security.app_sandbox`
+/// wird der Dienst abgeschottet und erhält nur die Geräte und Adressfamilien
+/// seiner Entitlements.
+pub fn systemd_service_unit(
+    info: &BundleInfoConfigFile,
+    bundle_path: &Path,
+    scope: SystemdScope,
+) -> Result<String, BundleError> {
+    let UnitPaths { bundle, content, executable, bundle_env } = unit_paths(info, bundle_path)?;
+    let identifier = &info.identifier;
+    let mut env = bundle_env.clone();
+
+    let mut exec_start = vec![quote_exec_argument(&executable)];
+    for argument in &info.launch.arguments {
+        check_unit_value("launch argument", argument)?;
+        let expanded = to_unit_string(expand(&escape_specifiers(argument), &bundle_env)?)?;
+        exec_start.push(quote_exec_argument(&expanded));
+    }
+
+    let mut declared: Vec<_> = info.launch.environment.iter().collect();
+    declared.sort();
+    for (key, value) in declared {
+        check_unit_value("launch environment variable", key)?;
+        check_unit_value("launch environment value", value)?;
+        let expanded = expand(&escape_specifiers(value), &bundle_env)?;
+        env.retain(|(k, _)| k != key.as_str());
+        env.push((OsString::from(key), expanded));
+    }
+
+    let working_directory = match &info.launch.working_directory {
+        Some(dir) => {
+            check_unit_value("launch working_directory", dir)?;
+            let dir = to_unit_string(expand(&escape_specifiers(dir), &bundle_env)?)?;
+            if dir.starts_with('/') { dir } else { format!("{}/{}", content, dir) }
+        }
+        None => content.clone(),
+    };
+
+    // Die Ziele gibt es nur beim System-Manager
+    let mut after: Vec<String> = Vec::new();
+    let mut wants: Vec<String> = Vec::new();
+    for mode in info.app_services.background_modes.iter().filter(|_| scope == SystemdScope::System) {
+        for &target in background_mode_dependencies(mode) {
+            if !after.iter().any(|t| t == target) {
+                after.push(target.to_string());
+                wants.push(target.to_string());
+            }
+        }
+    }
+    let service = info.service.clone().unwrap_or_default();
+    let mut requires: Vec<String> = Vec::new();
+    for dependency in service.dependencies() {
+        let dependency = unit_name(dependency)?;
+        if !after.contains(&dependency) {
+            after.push(dependency);
+        }
+    }
+    for dependency in &service.requires {
+        requires.push(unit_name(dependency)?);
+    }
+
+    let mut unit = String::new();
+    let _ = writeln!(unit, "# Generated from {}/Content/Info.json, do not edit.", bundle);
+    let _ = writeln!(unit, "[Unit]");
+    let _ = writeln!(unit, "Description={}", escape_specifiers(&info.name));
+    for (key, units) in [("After", &after), ("Wants", &wants), ("Requires", &requires)] {
+        if !units.is_empty() {
+            let _ = writeln!(unit, "{}={}", key, units.join(" "));
+        }
+    }
+
+    let _ = writeln!(unit, "\n[Service]");
+    let _ = writeln!(unit, "Type=simple");
+    let _ = writeln!(unit, "ExecStart={}", exec_start.join(" "));
+    let _ = writeln!(unit, "WorkingDirectory={}", working_directory);
+    for (key, value) in &env {
+        let _ = writeln!(
+            unit,
+            "Environment={}",
+            quote_environment(&key.to_string_lossy(), &to_unit_string(value.clone())?)
+        );
+    }
+    let _ = writeln!(unit, "StateDirectory={}", identifier);
+    let _ = writeln!(unit, "CacheDirectory={}", identifier);
+    let _ = writeln!(unit, "ConfigurationDirectory={}", identifier);
+    let restart = match service.restart {
+        RestartPolicy::Never => "no",
+        RestartPolicy::OnFailure => "on-failure",
+        RestartPolicy::Always => "always",
+    };
+    let socket_units: Vec<String> =
+        service.sockets.iter().map(|socket| socket_unit_name(identifier, &socket.name)).collect();
+    if !socket_units.is_empty() {
+        let _ = writeln!(unit, "Sockets={}", socket_units.join(" "));
+    }
+    let _ = writeln!(unit, "Restart={}", restart);
+    if let Some(delay) = service.restart_delay_seconds {
+        let _ = writeln!(unit, "RestartSec={}", delay);
+    }
+    if let Some(timeout) = service.start_timeout_seconds {
+        let _ = writeln!(unit, "TimeoutStartSec={}", timeout);
+    }
+
+    if info.security.app_sandbox {
+        write_sandboxing(&mut unit, &info.entitlements);
+    }
+
+    // Mit Sockets startet der Dienst erst bei der ersten Verbindung
+    let _ = writeln!(unit, "\n[Install]");
+    if socket_units.is_empty() {
+        let _ = writeln!(unit, "WantedBy={}", scope.wanted_by());
+    } else {
+        let _ = writeln!(unit, "Also={}", socket_units.join(" "));
+    }
+    Ok(unit)
+}
+
+/// Erzeugt je eine Socket-Unit pro Eintrag in `service.sockets` als
+/// (Unit-Name, Text). `FileDescriptorName=` ist der deklarierte Name, den
+/// [`Bundle::listen_sockets`] über `LISTEN_FDNAMES` wiederfindet. Socket-Pfade
+/// dürfen die Datenverzeichnisse nicht verwenden, da sie unter systemd woanders liegen.
+pub fn systemd_socket_units(
+    info: &BundleInfoConfigFile,
+    bundle_path: &Path,
+) -> Result<Vec<(String, String)>, BundleError> {
+    let UnitPaths { bundle, bundle_env, .. } = unit_paths(info, bundle_path)?;
+    let identifier = &info.identifier;
+    let Some(service) = &info.service else {
+        return Ok(Vec::new());
+    };
+
+    let mut units = Vec::new();
+    for socket in &service.sockets {
+        check_unit_value("socket name", &socket.name)?;
+        if let Some(address) = &socket.address {
+            check_unit_value("socket address", address)?;
+        }
+        let listen = match (&socket.path, socket.port) {
+            (Some(path), _) => {
+                check_unit_value("socket path", path)?;
+                check_socket_path(path)
+                    .map_err(|e| BundleError::InvalidFormat(format!("socket '{}': {}", socket.name, e)))?;
+                to_unit_string(expand(&escape_specifiers(path), &bundle_env)?)?
+            }
+            (None, Some(port)) => match &socket.address {
+                Some(address) if address.contains(':') => format!("[{}]:{}", address, port),
+                Some(address) => format!("{}:{}", address, port),
+                None => port.to_string(),
+            },
+            (None, None) => {
+                return Err(BundleError::InvalidFormat(format!(
+                    "socket '{}' declares neither path nor port",
+                    socket.name
+                )));
+            }
+        };
+
+        let mut unit = String::new();
+        let _ = writeln!(unit, "# Generated from {}/Content/Info.json, do not edit.", bundle);
+        let _ = writeln!(unit, "[Unit]");
+        let _ = writeln!(unit, "Description={} ({})", escape_specifiers(&info.name), socket.name);
+        let _ = writeln!(unit, "\n[Socket]");
+        let _ = writeln!(unit, "ListenStream={}", listen);
+        let _ = writeln!(unit, "FileDescriptorName={}", socket.name);
+        let _ = writeln!(unit, "Service={}", unit_name(identifier)?);
+        let _ = writeln!(unit, "\n[Install]");
+        let _ = writeln!(unit, "WantedBy=sockets.target");
+        units.push((socket_unit_name(identifier, &socket.name), unit));
+    }
+    Ok(units)
+}
+
+/// Schreibt die Sandbox-Direktiven für `entitlements`.
+fn write_sandboxing(unit: &mut String, entitlements: &[EntitlementType]) {
+    let mut devices: Vec<&str> = Vec::new();
+    let mut families: Vec<&str> = vec!["AF_UNIX"];
+    for entitlement in entitlements {
+        for &device in entitlement_devices(entitlement) {
+            if !devices.contains(&device) {
+                devices.push(device);
+            }
+        }
+        for &family in entitlement_address_families(entitlement) {
+            if !families.contains(&family) {
+                families.push(family);
+            }
+        }
+    }
+    let files = entitlements.iter().any(|e| matches!(e, EntitlementType::Files));
+    let audio = entitlements.iter().any(|e| matches!(e, EntitlementType::Microphone));
+
+    let _ = writeln!(unit, "NoNewPrivileges=yes");
+    let _ = writeln!(unit, "ProtectSystem=strict");
+    let _ = writeln!(unit, "ProtectHome={}", if files { "no" } else { "read-only" });
+    let _ = writeln!(unit, "PrivateTmp=yes");
+    let _ = writeln!(unit, "ProtectKernelTunables=yes");
+    let _ = writeln!(unit, "ProtectKernelModules=yes");
+    let _ = writeln!(unit, "ProtectControlGroups=yes");
+    let _ = writeln!(unit, "RestrictSUIDSGID=yes");
+    let _ = writeln!(unit, "LockPersonality=yes");
+    if devices.is_empty() {
+        let _ = writeln!(unit, "PrivateDevices=yes");
+    } else {
+        let _ = writeln!(unit, "DevicePolicy=closed");
+        for device in &devices {
+            let _ = writeln!(unit, "DeviceAllow={} rw", device);
+        }
+    }
+    if audio {
+        let _ = writeln!(unit, "SupplementaryGroups=audio");
+    }
+    if families.len() == 1 {
+        let _ = writeln!(unit, "PrivateNetwork=yes");
+    }
+    let _ = writeln!(unit, "RestrictAddressFamilies={}", families.join(" "));
+}
+
+impl Bundle {
+    /// Name der systemd-Unit dieses Bundles, `<identifier>.service`.
+    pub fn systemd_unit_name(&self) -> Result<String, BundleError> {
+        unit_name(self.identifier()?)
+    }
+
+    /// Text der systemd-Unit, siehe [`systemd_service_unit`].
+    /// Nur für Service-Bundles.
+    pub fn systemd_unit(&self, scope: SystemdScope) -> Result<String, BundleError> {
+        if self.kind() != BundleKind::Service {
+            return Err(BundleError::InvalidFormat(format!(
+                "'{}' is not a service bundle",
+                self.path().display()
+            )));
+        }
+        systemd_service_unit(self.info()?, self.path(), scope)
+    }
+
+    /// Socket-Units dieses Bundles, siehe [`systemd_socket_units`].
+    pub fn systemd_socket_units(&self) -> Result<Vec<(String, String)>, BundleError> {
+        systemd_socket_units(self.info()?, self.path())
+    }
+
+    /// Schreibt die Service-Unit und alle Socket-Units nach `directory` und
+    /// liefert ihre Pfade, die Service-Unit zuerst. Die Dateien werden atomar
+    /// ersetzt; `systemctl daemon-reload` ist Sache des Aufrufers.
+    pub fn write_systemd_unit(&self, directory: &Path, scope: SystemdScope) -> Result<Vec<PathBuf>, BundleError> {
+        let mut units = vec![(self.systemd_unit_name()?, self.systemd_unit(scope)?)];
+        units.extend(self.systemd_socket_units()?);
+
+        fs::create_dir_all(directory)?;
+        let mut paths = Vec::new();
+        for (name, text) in units {
+            let path = directory.join(&name);
+            write_atomically(&path, text.as_bytes(), None)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
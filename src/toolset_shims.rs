@@ -0,0 +1,342 @@
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::{symlink_atomically, write_atomically};
+use crate::executables::is_valid_executable_name;
+use crate::{xdg, Bundle, BundleError, BundleExecutable, BundleKind, BundleRegistry};
+
+/// Kennzeichnet ein erzeugtes Shim-Skript; dahinter steht der Bundle-Pfad.
+const SHIM_MARKER: &str = "# bundle-shim: ";
+
+/// Form der erzeugten Einträge im bin-Verzeichnis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShimKind {
+    /// Shell-Skript, das die `BUNDLE_*`-Variablen setzt und das Executable per `exec` startet.
+    Script,
+    /// Symlink direkt auf das Executable, ohne Bundle-Umgebung.
+    Symlink,
+}
+
+/// Ein von uns erzeugter Eintrag im bin-Verzeichnis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shim {
+    pub name: String,
+    pub path: PathBuf,
+    /// Toolset, zu dem der Shim gehört.
+    pub bundle_path: PathBuf,
+}
+
+/// Ein Befehlsname, der nicht eindeutig vergeben werden konnte.
+#[derive(Debug, Clone)]
+pub struct ShimConflict {
+    pub name: String,
+    /// Toolset, das den Namen erhalten hat, oder `None`, wenn eine fremde Datei ihn belegt.
+    pub winner: Option<PathBuf>,
+    /// Toolsets, die den Namen ebenfalls deklarieren und leer ausgehen.
+    pub losers: Vec<PathBuf>,
+}
+
+/// Ergebnis einer Installation oder Bereinigung.
+#[derive(Debug, Default)]
+pub struct ShimReport {
+    /// Neu geschriebene oder aktualisierte Shims.
+    pub installed: Vec<Shim>,
+    /// Bereits aktuelle Shims.
+    pub unchanged: Vec<Shim>,
+    /// Entfernte veraltete Shims.
+    pub removed: Vec<Shim>,
+    pub conflicts: Vec<ShimConflict>,
+}
+
+/// Ein bin-Verzeichnis, in das Toolset-Befehle als Shims gelegt werden.
+///
+/// Es werden nur Einträge angefasst, die als Shim erkennbar sind: Skripte mit
+/// Shim-Markierung und Symlinks in ein `.toolsetd`-Bundle. Andere Dateien
+/// bleiben unverändert und werden als Konflikt gemeldet.
+#[derive(Debug, Clone)]
+pub struct ShimDirectory {
+    bin_dir: PathBuf,
+    kind: ShimKind,
+}
+
+fn shell_quote(value: &OsStr) -> Vec<u8> {
+    let mut quoted = vec![b'\''];
+    for &byte in value.as_bytes() {
+        if byte == b'\'' {
+            quoted.extend_from_slice(b"'\\''");
+        } else {
+            quoted.push(byte);
+        }
+    }
+    quoted.push(b'\'');
+    quoted
+}
+
+/// Nächster Vorfahre von `path` mit der Endung `.toolsetd`.
+pub(crate) fn owning_toolset(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.extension().is_some_and(|ext| ext == BundleKind::Toolset.extension()))
+        .map(Path::to_path_buf)
+}
+
+/// Schlägt fehl, wenn `bundle` kein Toolset ist.
+pub(crate) fn require_toolset(bundle: &Bundle) -> Result<(), BundleError> {
+    if bundle.kind() != BundleKind::Toolset {
+        return Err(BundleError::InvalidFormat(format!(
+            "'{}' is not a toolset bundle",
+            bundle.path().display()
+        )));
+    }
+    Ok(())
+}
+
+/// Schreibt `content` atomar nach `path` und macht die Datei ausführbar.
+fn write_script(path: &Path, content: &[u8]) -> Result<(), BundleError> {
+    Ok(write_atomically(path, content, Some(fs::Permissions::from_mode(0o755)))?)
+}
+
+/// Ersetzt `path` atomar durch einen Symlink auf `target`.
+fn write_symlink(path: &Path, target: &Path) -> Result<(), BundleError> {
+    Ok(symlink_atomically(path, target)?)
+}
+
+impl ShimDirectory {
+    pub fn new(bin_dir: impl Into<PathBuf>, kind: ShimKind) -> ShimDirectory {
+        ShimDirectory { bin_dir: bin_dir.into(), kind }
+    }
+
+    /// `~/.local/bin` mit Skript-Shims.
+    pub fn user_default() -> Option<ShimDirectory> {
+        xdg::bin_home().map(|dir| ShimDirectory::new(dir, ShimKind::Script))
+    }
+
+    pub fn bin_dir(&self) -> &Path {
+        &self.bin_dir
+    }
+
+    pub fn kind(&self) -> ShimKind {
+        self.kind
+    }
+
+    /// Liest den Eintrag `path` und liefert den Shim, falls er von uns stammt.
+    fn read_shim(path: &Path) -> Option<Shim> {
+        let name = path.file_name()?.to_str()?.to_string();
+        let meta = fs::symlink_metadata(path).ok()?;
+        let bundle_path = if meta.file_type().is_symlink() {
+            owning_toolset(&fs::read_link(path).ok()?)?
+        } else if meta.is_file() {
+            let content = fs::read(path).ok()?;
+            let line = content
+                .split(|&b| b == b'\n')
+                .take(3)
+                .find_map(|line| line.strip_prefix(SHIM_MARKER.as_bytes()))?;
+            PathBuf::from(OsStr::from_bytes(line))
+        } else {
+            return None;
+        };
+        Some(Shim { name, path: path.to_path_buf(), bundle_path })
+    }
+
+    /// Alle Shims im bin-Verzeichnis, nach Namen sortiert.
+    pub fn shims(&self) -> Result<Vec<Shim>, BundleError> {
+        if !self.bin_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut shims: Vec<Shim> = fs::read_dir(&self.bin_dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| Self::read_shim(&entry.path()))
+            .collect();
+        shims.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(shims)
+    }
+
+    /// Inhalt des Shim-Skripts für `executable` aus `bundle`.
+    fn script_for(bundle: &Bundle, executable: &BundleExecutable) -> Result<Vec<u8>, BundleError> {
+        let command = bundle.command_for(executable.path.clone())?;
+        let mut script = b"#!/bin/sh\n".to_vec();
+        script.extend_from_slice(SHIM_MARKER.as_bytes());
+        script.extend_from_slice(bundle.path().as_os_str().as_bytes());
+        script.extend_from_slice(b"\n# generated, do not edit\n");
+        for (key, value) in command.environment() {
+            script.extend_from_slice(b"export ");
+            script.extend_from_slice(key.as_bytes());
+            script.push(b'=');
+            script.extend(shell_quote(value));
+            script.push(b'\n');
+        }
+        script.extend_from_slice(b"exec ");
+        script.extend(shell_quote(executable.path.as_os_str()));
+        script.extend_from_slice(b" \"$@\"\n");
+        Ok(script)
+    }
+
+    /// Prüft, ob `path` bereits genau diesen Shim enthält.
+    fn is_current(&self, path: &Path, bundle: &Bundle, executable: &BundleExecutable) -> Result<bool, BundleError> {
+        let Ok(meta) = fs::symlink_metadata(path) else {
+            return Ok(false);
+        };
+        Ok(match self.kind {
+            ShimKind::Symlink => {
+                meta.file_type().is_symlink() && fs::read_link(path).ok().as_ref() == Some(&executable.path)
+            }
+            ShimKind::Script => meta.is_file() && fs::read(path).ok() == Some(Self::script_for(bundle, executable)?),
+        })
+    }
+
+    fn write_shim(&self, path: &Path, bundle: &Bundle, executable: &BundleExecutable) -> Result<(), BundleError> {
+        match self.kind {
+            ShimKind::Script => write_script(path, &Self::script_for(bundle, executable)?),
+            ShimKind::Symlink => write_symlink(path, &executable.path),
+        }
+    }
+
+    /// Gleicht das bin-Verzeichnis mit `desired` ab. Shims aus `scope` (alle, wenn
+    /// `None`), die nicht mehr gewünscht sind, werden entfernt.
+    fn apply(
+        &self,
+        desired: BTreeMap<String, (&Bundle, BundleExecutable)>,
+        scope: Option<&Path>,
+        mut report: ShimReport,
+    ) -> Result<ShimReport, BundleError> {
+        fs::create_dir_all(&self.bin_dir)?;
+
+        for shim in self.shims()? {
+            if scope.is_some_and(|bundle_path| shim.bundle_path != bundle_path) {
+                continue;
+            }
+            let still_wanted = desired
+                .get(&shim.name)
+                .is_some_and(|(bundle, _)| bundle.path() == shim.bundle_path);
+            if !still_wanted {
+                fs::remove_file(&shim.path)?;
+                report.removed.push(shim);
+            }
+        }
+
+        for (name, (bundle, executable)) in desired {
+            let path = self.bin_dir.join(&name);
+            let shim = Shim { name: name.clone(), path: path.clone(), bundle_path: bundle.path().to_path_buf() };
+
+            if fs::symlink_metadata(&path).is_ok() {
+                match Self::read_shim(&path) {
+                    // Fremde Datei: nicht überschreiben
+                    None => {
+                        report.conflicts.push(ShimConflict {
+                            name,
+                            winner: None,
+                            losers: vec![shim.bundle_path],
+                        });
+                        continue;
+                    }
+                    // Gehört einem anderen Toolset, das hier nicht neu verteilt wird
+                    Some(existing) if existing.bundle_path != shim.bundle_path => {
+                        report.conflicts.push(ShimConflict {
+                            name,
+                            winner: Some(existing.bundle_path),
+                            losers: vec![shim.bundle_path],
+                        });
+                        continue;
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            if self.is_current(&path, bundle, &executable)? {
+                report.unchanged.push(shim);
+            } else {
+                self.write_shim(&path, bundle, &executable)?;
+                if self.kind == ShimKind::Script {
+                    bundle.create_data_directories()?;
+                }
+                report.installed.push(shim);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Ordnet jedem Befehlsnamen ein Toolset zu. Bei mehrfach deklarierten
+    /// Namen gewinnt das erste Toolset in `toolsets`.
+    fn collect<'a>(
+        toolsets: &[&'a Bundle],
+        report: &mut ShimReport,
+    ) -> Result<BTreeMap<String, (&'a Bundle, BundleExecutable)>, BundleError> {
+        let mut desired: BTreeMap<String, (&Bundle, BundleExecutable)> = BTreeMap::new();
+        let mut conflicts: BTreeMap<String, ShimConflict> = BTreeMap::new();
+        for &bundle in toolsets {
+            for executable in bundle.executables()? {
+                if !is_valid_executable_name(&executable.name) {
+                    continue;
+                }
+                match desired.get(&executable.name) {
+                    Some((winner, _)) if winner.path() != bundle.path() => {
+                        conflicts
+                            .entry(executable.name.clone())
+                            .or_insert_with(|| ShimConflict {
+                                name: executable.name.clone(),
+                                winner: Some(winner.path().to_path_buf()),
+                                losers: Vec::new(),
+                            })
+                            .losers
+                            .push(bundle.path().to_path_buf());
+                    }
+                    Some(_) => {}
+                    None => {
+                        desired.insert(executable.name.clone(), (bundle, executable));
+                    }
+                }
+            }
+        }
+        report.conflicts.extend(conflicts.into_values());
+        Ok(desired)
+    }
+
+    /// Legt Shims für alle Befehle von `toolset` an und entfernt dessen veraltete Shims.
+    /// Shims anderer Toolsets bleiben unberührt; belegte Namen werden als Konflikt gemeldet.
+    pub fn install(&self, toolset: &Bundle) -> Result<ShimReport, BundleError> {
+        require_toolset(toolset)?;
+        let mut report = ShimReport::default();
+        let desired = Self::collect(&[toolset], &mut report)?;
+        self.apply(desired, Some(toolset.path()), report)
+    }
+
+    /// Entfernt alle Shims, die auf das Toolset unter `bundle_path` zeigen.
+    /// Das Bundle muss dafür nicht mehr existieren.
+    pub fn uninstall(&self, bundle_path: &Path) -> Result<ShimReport, BundleError> {
+        let mut report = ShimReport::default();
+        for shim in self.shims()? {
+            if shim.bundle_path == bundle_path {
+                fs::remove_file(&shim.path)?;
+                report.removed.push(shim);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Gleicht das bin-Verzeichnis mit genau den Toolsets in `toolsets` ab:
+    /// fehlende Shims werden angelegt, veraltete aktualisiert und Shims nicht
+    /// mehr vorhandener Toolsets entfernt. Bei gleichen Befehlsnamen gewinnt
+    /// das frühere Toolset in `toolsets`.
+    pub fn sync(&self, toolsets: &[&Bundle]) -> Result<ShimReport, BundleError> {
+        for toolset in toolsets {
+            require_toolset(toolset)?;
+        }
+        let mut report = ShimReport::default();
+        let desired = Self::collect(toolsets, &mut report)?;
+        self.apply(desired, None, report)
+    }
+}
+
+impl BundleRegistry {
+    /// Gleicht `shims` mit allen wirksamen Toolsets der Registry ab.
+    /// Bei gleichen Befehlsnamen gewinnt die höhere Suchstufe.
+    pub fn sync_shims(&self, shims: &ShimDirectory) -> Result<ShimReport, BundleError> {
+        let mut entries: Vec<_> = self.bundles_of_kind(BundleKind::Toolset).collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.scope));
+        let toolsets = entries.iter().map(|entry| entry.open()).collect::<Result<Vec<_>, _>>()?;
+        shims.sync(&toolsets.iter().collect::<Vec<_>>())
+    }
+}
@@ -0,0 +1,284 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::atomic_file::symlink_atomically;
+use crate::toolset_shims::{owning_toolset, require_toolset};
+use crate::{xdg, Bundle, BundleError};
+
+/// Verzeichnis der Man-Pages unter `Content/Resources`, gegliedert wie
+/// `/usr/share/man`: `man/man1/tool.1`, `man/man5/tool.conf.5.gz`.
+pub const MAN_PAGES_DIRECTORY: &str = "man";
+
+/// Verzeichnis der Shell-Completions unter `Content/Resources`, mit je einem
+/// Unterverzeichnis pro Shell: `completions/bash/tool`, `completions/zsh/_tool`,
+/// `completions/fish/tool.fish`.
+pub const COMPLETIONS_DIRECTORY: &str = "completions";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub const ALL: [Shell; 3] = [Shell::Bash, Shell::Zsh, Shell::Fish];
+
+    /// Name des Unterverzeichnisses unter [`COMPLETIONS_DIRECTORY`].
+    pub fn directory_name(self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+        }
+    }
+
+    /// Befehlsname zu einem Completion-Dateinamen nach der Konvention der Shell.
+    fn command_for_file(self, file_name: &str) -> Option<&str> {
+        match self {
+            Shell::Bash => Some(file_name),
+            Shell::Zsh => file_name.strip_prefix('_'),
+            Shell::Fish => file_name.strip_suffix(".fish"),
+        }
+        .filter(|command| !command.is_empty())
+    }
+}
+
+/// Eine Man-Page im Bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManPage {
+    /// Dateiname, z.B. `tool.1` oder `tool.conf.5.gz`.
+    pub file_name: String,
+    /// Abschnitt aus dem Verzeichnisnamen, z.B. `1` für `man1`.
+    pub section: String,
+    pub path: PathBuf,
+}
+
+/// Ein Completion-Skript im Bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellCompletion {
+    pub shell: Shell,
+    /// Befehl, den das Skript vervollständigt.
+    pub command: String,
+    pub file_name: String,
+    pub path: PathBuf,
+}
+
+/// Dateien eines Verzeichnisses, nach Namen sortiert; fehlt es, ist die Liste leer.
+fn files_in(dir: &Path) -> Result<Vec<(String, PathBuf)>, BundleError> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<(String, PathBuf)> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| Some((path.file_name()?.to_str()?.to_string(), path)))
+        .filter(|(name, _)| !name.starts_with('.'))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+impl Bundle {
+    /// Alle Man-Pages unter `Resources/man/man<abschnitt>/`.
+    pub fn man_pages(&self) -> Result<Vec<ManPage>, BundleError> {
+        let root = self.resources_path().join(MAN_PAGES_DIRECTORY);
+        if !root.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut sections: Vec<(String, PathBuf)> = fs::read_dir(&root)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let section = entry.file_name().to_str()?.strip_prefix("man")?.to_string();
+                (!section.is_empty() && entry.path().is_dir()).then(|| (section, entry.path()))
+            })
+            .collect();
+        sections.sort();
+
+        let mut pages = Vec::new();
+        for (section, dir) in sections {
+            for (file_name, path) in files_in(&dir)? {
+                pages.push(ManPage { file_name, section: section.clone(), path });
+            }
+        }
+        Ok(pages)
+    }
+
+    /// Alle Completion-Skripte unter `Resources/completions/<shell>/`.
+    pub fn shell_completions(&self) -> Result<Vec<ShellCompletion>, BundleError> {
+        let root = self.resources_path().join(COMPLETIONS_DIRECTORY);
+        let mut completions = Vec::new();
+        for shell in Shell::ALL {
+            for (file_name, path) in files_in(&root.join(shell.directory_name()))? {
+                let Some(command) = shell.command_for_file(&file_name) else {
+                    continue;
+                };
+                completions.push(ShellCompletion { shell, command: command.to_string(), file_name, path });
+            }
+        }
+        Ok(completions)
+    }
+}
+
+/// Eine Zieldatei, die nicht verlinkt werden konnte.
+#[derive(Debug, Clone)]
+pub struct SupportFileConflict {
+    pub path: PathBuf,
+    /// Toolset, dem der vorhandene Link gehört, oder `None` bei einer fremden Datei.
+    pub owner: Option<PathBuf>,
+}
+
+/// Ergebnis von [`SupportFileDirectories::install`] und [`SupportFileDirectories::uninstall`].
+#[derive(Debug, Default)]
+pub struct SupportFileReport {
+    pub linked: Vec<PathBuf>,
+    pub unchanged: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+    pub conflicts: Vec<SupportFileConflict>,
+}
+
+/// Zielverzeichnisse für Man-Pages und Completions eines Benutzers oder Systems.
+#[derive(Debug, Clone)]
+pub struct SupportFileDirectories {
+    /// Wurzel mit `man<abschnitt>`-Unterverzeichnissen, Teil von MANPATH.
+    pub man: PathBuf,
+    pub bash: PathBuf,
+    pub zsh: PathBuf,
+    pub fish: PathBuf,
+}
+
+impl SupportFileDirectories {
+    /// Benutzerverzeichnisse unter `$XDG_DATA_HOME`: `man` (von man-db neben
+    /// `~/.local/bin` gefunden), `bash-completion/completions`,
+    /// `zsh/site-functions` (muss in `fpath` stehen) und `fish/vendor_completions.d`.
+    pub fn user_default() -> Option<SupportFileDirectories> {
+        let data = xdg::data_home()?;
+        Some(SupportFileDirectories {
+            man: data.join("man"),
+            bash: data.join("bash-completion/completions"),
+            zsh: data.join("zsh/site-functions"),
+            fish: data.join("fish/vendor_completions.d"),
+        })
+    }
+
+    pub fn completions(&self, shell: Shell) -> &Path {
+        match shell {
+            Shell::Bash => &self.bash,
+            Shell::Zsh => &self.zsh,
+            Shell::Fish => &self.fish,
+        }
+    }
+
+    /// Gewünschte Links (Ziel, Quelle) für die Dateien von `toolset`.
+    fn links_for(&self, toolset: &Bundle) -> Result<Vec<(PathBuf, PathBuf)>, BundleError> {
+        let mut links: Vec<(PathBuf, PathBuf)> = toolset
+            .man_pages()?
+            .into_iter()
+            .map(|page| (self.man.join(format!("man{}", page.section)).join(page.file_name), page.path))
+            .collect();
+        links.extend(
+            toolset
+                .shell_completions()?
+                .into_iter()
+                .map(|completion| (self.completions(completion.shell).join(completion.file_name), completion.path)),
+        );
+        Ok(links)
+    }
+
+    /// Alle Verzeichnisse, in denen Links liegen können.
+    fn link_directories(&self) -> Result<Vec<PathBuf>, BundleError> {
+        let mut dirs: Vec<PathBuf> = Shell::ALL.iter().map(|&shell| self.completions(shell).to_path_buf()).collect();
+        if self.man.is_dir() {
+            for entry in fs::read_dir(&self.man)?.flatten() {
+                if entry.file_name().to_str().is_some_and(|name| name.starts_with("man")) {
+                    dirs.push(entry.path());
+                }
+            }
+        }
+        Ok(dirs)
+    }
+
+    /// Links in den Zielverzeichnissen, die in das Toolset unter `bundle_path` zeigen.
+    fn links_into(&self, bundle_path: &Path) -> Result<Vec<PathBuf>, BundleError> {
+        let mut links = Vec::new();
+        for dir in self.link_directories()? {
+            let Ok(entries) = fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                if link_owner(&entry.path()).as_deref() == Some(bundle_path) {
+                    links.push(entry.path());
+                }
+            }
+        }
+        links.sort();
+        Ok(links)
+    }
+
+    /// Verlinkt Man-Pages und Completions von `toolset` und entfernt dessen
+    /// Links auf nicht mehr vorhandene Dateien. Mehrfaches Aufrufen ändert
+    /// nichts. Vorhandene fremde Dateien und Links anderer Toolsets werden
+    /// nicht überschrieben, sondern als Konflikt gemeldet.
+    pub fn install(&self, toolset: &Bundle) -> Result<SupportFileReport, BundleError> {
+        require_toolset(toolset)?;
+        let mut report = SupportFileReport::default();
+        let links = self.links_for(toolset)?;
+
+        for stale in self.links_into(toolset.path())? {
+            if !links.iter().any(|(link, _)| *link == stale) {
+                fs::remove_file(&stale)?;
+                report.removed.push(stale);
+            }
+        }
+
+        for (link, source) in links {
+            match fs::symlink_metadata(&link) {
+                Err(_) => {}
+                Ok(meta) if meta.file_type().is_symlink() => {
+                    if fs::read_link(&link).ok().as_ref() == Some(&source) {
+                        report.unchanged.push(link);
+                        continue;
+                    }
+                    match link_owner(&link) {
+                        Some(owner) if owner == toolset.path() => {}
+                        owner => {
+                            report.conflicts.push(SupportFileConflict { path: link, owner });
+                            continue;
+                        }
+                    }
+                }
+                Ok(_) => {
+                    report.conflicts.push(SupportFileConflict { path: link, owner: None });
+                    continue;
+                }
+            }
+            if let Some(parent) = link.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            symlink_atomically(&link, &source)?;
+            report.linked.push(link);
+        }
+        Ok(report)
+    }
+
+    /// Entfernt alle Links in das Toolset unter `bundle_path`.
+    /// Das Bundle muss dafür nicht mehr existieren.
+    pub fn uninstall(&self, bundle_path: &Path) -> Result<SupportFileReport, BundleError> {
+        let mut report = SupportFileReport::default();
+        for link in self.links_into(bundle_path)? {
+            fs::remove_file(&link)?;
+            report.removed.push(link);
+        }
+        Ok(report)
+    }
+}
+
+/// Toolset, in das der Symlink `path` zeigt.
+fn link_owner(path: &Path) -> Option<PathBuf> {
+    let meta = fs::symlink_metadata(path).ok()?;
+    if !meta.file_type().is_symlink() {
+        return None;
+    }
+    owning_toolset(&fs::read_link(path).ok()?)
+}
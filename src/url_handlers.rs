@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use crate::{BundleError, BundleRegistry, HandlerPreferences, RegisteredBundle};
+
+/// Extrahiert das Scheme einer URL nach RFC 3986
+/// (`ALPHA *( ALPHA / DIGIT / "+" / "-" / "." ) ":"`), kleingeschrieben.
+pub fn url_scheme(url: &str) -> Option<String> {
+    let (scheme, _) = url.trim_start().split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then(|| scheme.to_ascii_lowercase())
+}
+
+/// Handler für ein URL-Scheme in Rangfolge.
+#[derive(Debug)]
+pub struct UrlSchemeHandlers<'a> {
+    pub scheme: String,
+    /// Alle Bundles, die das Scheme deklarieren; der erste Eintrag ist der Standard-Handler.
+    pub handlers: Vec<&'a RegisteredBundle>,
+    /// `true`, wenn der erste Eintrag aus den Benutzereinstellungen stammt.
+    pub preferred: bool,
+}
+
+impl<'a> UrlSchemeHandlers<'a> {
+    /// Der Handler, der die URL öffnen soll.
+    pub fn default_handler(&self) -> Option<&'a RegisteredBundle> {
+        self.handlers.first().copied()
+    }
+
+    /// Mehr als ein Bundle beansprucht das Scheme.
+    pub fn is_conflict(&self) -> bool {
+        self.handlers.len() > 1
+    }
+}
+
+/// Ein URL-Scheme, das von mehreren Bundles deklariert wird.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlSchemeConflict {
+    pub scheme: String,
+    /// Bezeichner der beteiligten Bundles in Rangfolge (ohne Benutzereinstellung).
+    pub identifiers: Vec<String>,
+}
+
+fn declares_scheme(bundle: &RegisteredBundle, scheme: &str) -> bool {
+    bundle.info.url_schemes.iter().any(|u| u.scheme.eq_ignore_ascii_case(scheme))
+}
+
+/// Rangfolge ohne Benutzereinstellung: höhere Suchstufe zuerst, dann Bezeichner.
+fn by_precedence(a: &&RegisteredBundle, b: &&RegisteredBundle) -> std::cmp::Ordering {
+    b.scope.cmp(&a.scope).then_with(|| a.identifier().cmp(b.identifier()))
+}
+
+impl BundleRegistry {
+    /// Alle wirksamen Bundles, die `scheme` deklarieren, in Rangfolge:
+    /// 1. der in `preferences` gewählte Standard-Handler, sofern er das Scheme deklariert,
+    /// 2. danach Bundles aus höheren Suchstufen ([`SearchScope`](crate::SearchScope)),
+    /// 3. bei Gleichstand alphabetisch nach Bezeichner.
+    pub fn handlers_for_scheme(&self, scheme: &str, preferences: &HandlerPreferences) -> UrlSchemeHandlers<'_> {
+        let scheme = scheme.to_ascii_lowercase();
+        let mut handlers: Vec<&RegisteredBundle> =
+            self.bundles().filter(|b| declares_scheme(b, &scheme)).collect();
+        handlers.sort_by(by_precedence);
+
+        let mut preferred = false;
+        if let Some(identifier) = preferences.url_handler(&scheme)
+            && let Some(pos) = handlers.iter().position(|b| b.identifier() == identifier)
+        {
+            let handler = handlers.remove(pos);
+            handlers.insert(0, handler);
+            preferred = true;
+        }
+        UrlSchemeHandlers { scheme, handlers, preferred }
+    }
+
+    /// Ermittelt die Handler für das Scheme von `url`.
+    /// Gibt `InvalidFormat` zurück, wenn die URL kein gültiges Scheme hat.
+    pub fn handlers_for_url(&self, url: &str, preferences: &HandlerPreferences) -> Result<UrlSchemeHandlers<'_>, BundleError> {
+        let scheme = url_scheme(url)
+            .ok_or_else(|| BundleError::InvalidFormat(format!("'{}' has no valid URL scheme", url)))?;
+        Ok(self.handlers_for_scheme(&scheme, preferences))
+    }
+
+    /// Alle Schemes, die von mehr als einem wirksamen Bundle deklariert werden,
+    /// nach Scheme sortiert.
+    pub fn url_scheme_conflicts(&self) -> Vec<UrlSchemeConflict> {
+        let mut by_scheme: BTreeMap<String, Vec<&RegisteredBundle>> = BTreeMap::new();
+        for bundle in self.bundles() {
+            for url_scheme in &bundle.info.url_schemes {
+                let claimants = by_scheme.entry(url_scheme.scheme.to_ascii_lowercase()).or_default();
+                // Ein Bundle, das ein Scheme doppelt deklariert, zählt nur einmal
+                if !claimants.iter().any(|b| std::ptr::eq(*b, bundle)) {
+                    claimants.push(bundle);
+                }
+            }
+        }
+
+        by_scheme
+            .into_iter()
+            .filter(|(_, bundles)| bundles.len() > 1)
+            .map(|(scheme, mut bundles)| {
+                bundles.sort_by(by_precedence);
+                UrlSchemeConflict {
+                    scheme,
+                    identifiers: bundles.iter().map(|b| b.identifier().to_string()).collect(),
+                }
+            })
+            .collect()
+    }
+}
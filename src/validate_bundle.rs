@@ -2,7 +2,8 @@ use std::{fs, path::Path};
 use goblin::elf;
 use crate::{get_current_launched_bundle_path, get_loaded_bundle_info_config, BundleValidationError, BundleValidationResult};
 
-#[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Architecture {
     X86,      // i386
     X86_64,   // x86_64
@@ -11,6 +12,23 @@ pub enum Architecture {
     Other(u16),
 }
 
+impl Architecture {
+    /// Architektur, für die diese Bibliothek gebaut wurde (und damit die des Hosts).
+    pub fn host() -> Architecture {
+        if cfg!(target_arch = "x86") {
+            Architecture::X86
+        } else if cfg!(target_arch = "x86_64") {
+            Architecture::X86_64
+        } else if cfg!(target_arch = "aarch64") {
+            Architecture::ARM64
+        } else if cfg!(target_arch = "arm") {
+            Architecture::ARM
+        } else {
+            Architecture::Other(elf::header::EM_NONE)
+        }
+    }
+}
+
 pub fn detect_elf_architecture<P: AsRef<Path>>(path: P) -> Result<Architecture, Box<dyn std::error::Error>> {
     let data = fs::read(path)?;
     let elf = elf::Elf::parse(&data)?;
@@ -42,7 +60,7 @@ pub fn is_arm64<P: AsRef<Path>>(path: P) -> Result<bool, Box<dyn std::error::Err
 
 pub fn validate_bundle() -> Result<BundleValidationResult, BundleValidationError> {
     // Es wird versucht den Aktuellen Bundle Path zu ermitteln
-    let bundle_path = match get_current_launched_bundle_path() {
+    let _bundle_path = match get_current_launched_bundle_path() {
         Ok(Some(path)) => path,
         Ok(None) => {
             eprintln!("Kein Bundle-Pfad in der Config gefunden");
@@ -54,13 +72,19 @@ pub fn validate_bundle() -> Result<BundleValidationResult, BundleValidationError
     };
 
     // Es wird versucht die Aktuelle Konfiguration zu laden
-    let bunlde_config = match get_loaded_bundle_info_config() {
+    let bundle_config = match get_loaded_bundle_info_config() {
         Ok(config) => config,
         Err(e) => { return Err(BundleValidationError::ConfigLoadError(format!("{:?}", e))); }
     };
 
+    // Platzhalter im launch-Abschnitt müssen bekannt sein
+    bundle_config.launch.validate()?;
+    if let Some(service) = &bundle_config.service {
+        service.validate(&bundle_config.identifier)?;
+    }
+
     // evtl. Warnungen sammeln
-    let mut warnings = Vec::new();
+    let warnings = Vec::new();
     Ok(BundleValidationResult {
         is_valid: true,
         message: "Bundle ist gültig".to_string(),
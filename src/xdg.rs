@@ -0,0 +1,29 @@
+use std::path::{Path, PathBuf};
+
+/// Liest die XDG-Variable `var`, sonst `$HOME/<fallback>`.
+fn base_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(fallback)))
+}
+
+/// `$XDG_DATA_HOME` oder `~/.local/share`.
+pub(crate) fn data_home() -> Option<PathBuf> {
+    base_dir("XDG_DATA_HOME", ".local/share")
+}
+
+/// `$XDG_CACHE_HOME` oder `~/.cache`.
+pub(crate) fn cache_home() -> Option<PathBuf> {
+    base_dir("XDG_CACHE_HOME", ".cache")
+}
+
+/// `$XDG_CONFIG_HOME` oder `~/.config`.
+pub(crate) fn config_home() -> Option<PathBuf> {
+    base_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// `~/.local/bin`; dafür gibt es keine XDG-Variable.
+pub(crate) fn bin_home() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/bin"))
+}
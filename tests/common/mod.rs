@@ -0,0 +1,96 @@
+//! Gemeinsame Hilfen für die Integrationstests.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+use bundle::BundleKind;
+use serde_json::Value;
+
+/// Minimale gültige Info.json; `NAME` und `IDENTIFIER` werden ersetzt.
+pub const INFO_JSON: &str = r#"{
+    "name": "NAME",
+    "identifier": "IDENTIFIER",
+    "entry_point": "bin/app",
+    "metadata": { "category": "test" },
+    "icons": { "icon_16": "16.png", "icon_32": "32.png", "icon_128": "128.png", "launch_screen": "launch.png" },
+    "platforms": ["linux"],
+    "minimum_system_version": "1.0",
+    "device_family": ["pi"],
+    "entitlements": [],
+    "url_schemes": [],
+    "app_services": { "background_modes": [] },
+    "security": {
+        "app_sandbox": true,
+        "app_transport_security": { "allows_insecure_http": false, "exception_domains": {} },
+        "code_signature": { "team_id": "TEST", "entitlements_file": "" }
+    },
+    "fibyos": { "document_types": [] }
+}"#;
+
+pub fn info_json(name: &str, identifier: &str) -> String {
+    INFO_JSON.replace("NAME", name).replace("IDENTIFIER", identifier)
+}
+
+/// Temporäres Verzeichnis, das beim Verlassen des Tests entfernt wird.
+pub struct TempDir {
+    pub path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        let path = std::env::temp_dir().join(format!("bundle-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir { path }
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Legt unter `bundle` ein Bundle mit Config.json und Info.json an.
+pub fn create_bundle(bundle: &Path, name: &str, identifier: &str) {
+    let content = bundle.join("Content");
+    fs::create_dir_all(&content).unwrap();
+    fs::write(content.join("Config.json"), "{}").unwrap();
+    fs::write(content.join("Info.json"), info_json(name, identifier)).unwrap();
+}
+
+/// Legt unter `dir` das Bundle `<identifier>.<endung>` der Art `kind` an;
+/// `patch` passt die Info.json vor dem Schreiben an. Liefert den Bundle-Pfad.
+pub fn create_bundle_with(dir: &Path, kind: BundleKind, identifier: &str, patch: impl FnOnce(&mut Value)) -> PathBuf {
+    let bundle = dir.join(format!("{}.{}", identifier, kind.extension()));
+    create_bundle(&bundle, identifier, identifier);
+    let info_path = bundle.join("Content/Info.json");
+    let mut info: Value = serde_json::from_slice(&fs::read(&info_path).unwrap()).unwrap();
+    patch(&mut info);
+    fs::write(&info_path, serde_json::to_vec_pretty(&info).unwrap()).unwrap();
+    bundle
+}
+
+/// Legt eine ausführbare Datei mit `content` an, samt fehlender Verzeichnisse.
+pub fn write_executable(path: &Path, content: &str) {
+    use std::os::unix::fs::PermissionsExt;
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// Leitet die XDG-Verzeichnisse ins Temp-Verzeichnis um, damit angelegte
+/// Datenverzeichnisse dort landen. Vor dem ersten Zugriff des Tests aufrufen.
+pub fn isolate_xdg() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let base = std::env::temp_dir().join("bundle-test-xdg");
+        for (key, dir) in [("XDG_DATA_HOME", "data"), ("XDG_CACHE_HOME", "cache"), ("XDG_CONFIG_HOME", "config")] {
+            // Einmalig, bevor ein Test die Umgebung liest
+            unsafe { std::env::set_var(key, base.join(dir)) };
+        }
+    });
+}
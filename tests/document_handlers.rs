@@ -0,0 +1,95 @@
+//! Dokument-Handler: Zuordnung über Dateiendungen mit Vorrang für
+//! mehrteilige Endungen und Benutzereinstellungen.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use bundle::{file_extensions, BundleKind, BundleRegistry, DocumentHandler, HandlerPreferences, SearchRoot, SearchScope};
+use common::{create_bundle_with, TempDir};
+use serde_json::json;
+
+fn declare_types(root: &Path, identifier: &str, types: &[(&str, &[&str])]) {
+    let types: Vec<_> = types
+        .iter()
+        .map(|(name, extensions)| json!({ "name": name, "extensions": extensions, "icon_file": "doc.png" }))
+        .collect();
+    create_bundle_with(root, BundleKind::App, identifier, |info| info["fibyos"]["document_types"] = json!(types));
+}
+
+/// `org.example.archiver` öffnet `.tar.gz`, die übrigen nur `.gz` bzw. `.TXT`.
+fn registry(dir: &TempDir) -> BundleRegistry {
+    let system = dir.path.join("system");
+    let user = dir.path.join("user");
+    declare_types(&system, "org.example.gzip", &[("Gzip", &["gz"])]);
+    declare_types(&user, "org.example.archiver", &[("Archiv", &[".tar.gz"]), ("Kompr.", &["gz"])]);
+    declare_types(&user, "org.example.unzip", &[("Gzip", &["GZ"]), ("Text", &[".TXT"])]);
+    BundleRegistry::scan(vec![SearchRoot::new(SearchScope::System, system), SearchRoot::new(SearchScope::User, user)])
+}
+
+fn ranked(handlers: &[DocumentHandler<'_>]) -> Vec<(String, String)> {
+    handlers.iter().map(|h| (h.bundle.identifier().to_string(), h.extension.clone())).collect()
+}
+
+fn pairs(list: &[(&str, &str)]) -> Vec<(String, String)> {
+    list.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect()
+}
+
+#[test]
+fn extensions_are_listed_longest_first() {
+    assert_eq!(file_extensions(Path::new("/tmp/Backup.TAR.gz")), ["tar.gz", "gz"]);
+    assert_eq!(file_extensions(Path::new(".bashrc")), Vec::<String>::new());
+    assert_eq!(file_extensions(Path::new(".config.json")), ["json"]);
+    assert_eq!(file_extensions(Path::new("trailing.")), Vec::<String>::new());
+    assert_eq!(file_extensions(Path::new("/")), Vec::<String>::new());
+}
+
+#[test]
+fn specific_extension_ranks_before_scope() {
+    let dir = TempDir::new("document-handlers-rank");
+    let registry = registry(&dir);
+    let handlers = registry.handlers_for_file(Path::new("backup.tar.GZ"), &HandlerPreferences::default());
+
+    assert_eq!(
+        ranked(&handlers),
+        pairs(&[("org.example.archiver", "tar.gz"), ("org.example.unzip", "gz"), ("org.example.gzip", "gz")])
+    );
+    assert_eq!(handlers[0].display_name(), "Archiv");
+    assert!(registry.handlers_for_file(Path::new("notes.md"), &HandlerPreferences::default()).is_empty());
+}
+
+#[test]
+fn preference_for_most_specific_extension_wins() {
+    let dir = TempDir::new("document-handlers-preference");
+    let registry = registry(&dir);
+    let mut preferences = HandlerPreferences::default();
+    preferences.set_extension_handler(".GZ", "org.example.gzip");
+
+    let handlers = registry.handlers_for_file(Path::new("backup.tar.gz"), &preferences);
+    assert_eq!(handlers[0].bundle.identifier(), "org.example.gzip");
+
+    preferences.set_extension_handler("tar.gz", "org.example.archiver");
+    let handlers = registry.handlers_for_file(Path::new("backup.tar.gz"), &preferences);
+    assert_eq!(handlers[0].bundle.identifier(), "org.example.archiver");
+
+    // Ein bevorzugter Handler, der die Endung nicht öffnen kann, ändert nichts
+    preferences.set_extension_handler("txt", "org.example.gzip");
+    let handlers = registry.handlers_for_file(Path::new("README.txt"), &preferences);
+    assert_eq!(ranked(&handlers), pairs(&[("org.example.unzip", "txt")]));
+}
+
+#[test]
+fn icon_is_resolved_through_bundle_resources() {
+    let dir = TempDir::new("document-handlers-icon");
+    let registry = registry(&dir);
+    let handlers = registry.handlers_for_file(Path::new("a.txt"), &HandlerPreferences::default());
+    let handler = &handlers[0];
+    assert_eq!(handler.icon_file(), "doc.png");
+    assert_eq!(handler.icon_path(), None);
+
+    let icon = handler.bundle.path.join("Content/Resources/doc.png");
+    fs::create_dir_all(icon.parent().unwrap()).unwrap();
+    fs::write(&icon, "").unwrap();
+    assert_eq!(handler.icon_path(), Some(icon));
+}
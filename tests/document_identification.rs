@@ -0,0 +1,115 @@
+//! Erkennung von Dokumenttypen über Signaturen, Endungen und Konformität.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use bundle::{BundleKind, BundleRegistry, MatchReason, SearchRoot, SearchScope};
+use common::{create_bundle_with, TempDir};
+use serde_json::{json, Value};
+
+fn declare_types(root: &Path, identifier: &str, types: Value) {
+    create_bundle_with(root, BundleKind::App, identifier, |info| info["fibyos"]["document_types"] = types);
+}
+
+fn registry(dir: &TempDir) -> BundleRegistry {
+    let root = dir.path.join("bundles");
+    declare_types(&root, "org.example.viewer", json!([
+        { "name": "PNG", "extensions": ["png"], "icon_file": "", "mime_types": ["image/png"],
+          "signatures": [{ "hex": "89 50 4E 47" }] },
+        { "name": "PDF", "extensions": [], "icon_file": "", "mime_types": ["application/pdf"],
+          "signatures": [{ "offset": 0, "text": "%PDF-" }] }
+    ]));
+    declare_types(&root, "org.example.markdown", json!([
+        { "name": "Markdown", "extensions": ["md"], "icon_file": "",
+          "mime_types": ["text/markdown"], "conforms_to": ["text/x-source"] }
+    ]));
+    declare_types(&root, "org.example.source", json!([
+        { "name": "Source", "extensions": ["rs"], "icon_file": "",
+          "mime_types": ["text/x-source"], "conforms_to": ["TEXT/PLAIN"] }
+    ]));
+    declare_types(&root, "org.example.editor", json!([
+        { "name": "Text", "extensions": ["txt"], "icon_file": "", "mime_types": ["text/plain"] }
+    ]));
+    BundleRegistry::scan(vec![SearchRoot::new(SearchScope::User, root)])
+}
+
+fn identify(registry: &BundleRegistry, path: &Path) -> Vec<(String, MatchReason)> {
+    registry
+        .identify_file(path)
+        .unwrap()
+        .into_iter()
+        .map(|m| (m.document_type.name.clone(), m.reason))
+        .collect()
+}
+
+fn reasons(list: &[(&str, MatchReason)]) -> Vec<(String, MatchReason)> {
+    list.iter().map(|(name, reason)| (name.to_string(), *reason)).collect()
+}
+
+#[test]
+fn signature_wins_over_misleading_extension() {
+    let dir = TempDir::new("identify-signature");
+    let registry = registry(&dir);
+    let file = dir.path.join("image.txt");
+    fs::write(&file, b"\x89PNG\r\n\x1a\n\0\0").unwrap();
+
+    assert_eq!(
+        identify(&registry, &file),
+        reasons(&[("PNG", MatchReason::Signature), ("Text", MatchReason::Extension)])
+    );
+}
+
+#[test]
+fn conformance_is_followed_transitively() {
+    let dir = TempDir::new("identify-conformance");
+    let registry = registry(&dir);
+    let file = dir.path.join("README.md");
+    // Das Nullbyte verhindert die Texterkennung; `Text` passt nur über Source
+    fs::write(&file, "# Überschrift\n\0").unwrap();
+
+    assert_eq!(
+        identify(&registry, &file),
+        reasons(&[
+            ("Markdown", MatchReason::Extension),
+            ("Text", MatchReason::Conformance),
+            ("Source", MatchReason::Conformance),
+        ])
+    );
+}
+
+#[test]
+fn text_content_qualifies_plain_text_handlers() {
+    let dir = TempDir::new("identify-text");
+    let registry = registry(&dir);
+    let text = dir.path.join("notes");
+    fs::write(&text, "plain words").unwrap();
+    let binary = dir.path.join("blob");
+    fs::write(&binary, b"\0\x01\x02").unwrap();
+    let empty = dir.path.join("empty");
+    fs::write(&empty, b"").unwrap();
+
+    assert_eq!(identify(&registry, &text), reasons(&[("Text", MatchReason::Conformance)]));
+    assert!(identify(&registry, &binary).is_empty());
+    assert!(identify(&registry, &empty).is_empty());
+    assert!(registry.identify_file(&dir.path.join("missing")).is_err());
+}
+
+#[test]
+fn malformed_signatures_never_match() {
+    let dir = TempDir::new("identify-malformed");
+    let root = dir.path.join("bundles");
+    // Nicht-ASCII-Hexziffern haben früher beim Zerlegen in Paare eine Panik ausgelöst
+    declare_types(&root, "org.example.broken", json!([
+        { "name": "Umlaut", "extensions": [], "icon_file": "", "signatures": [{ "hex": "ä" }, { "hex": "0ä0" }] },
+        { "name": "Odd", "extensions": [], "icon_file": "", "signatures": [{ "hex": "504" }] },
+        { "name": "Far", "extensions": [], "icon_file": "", "signatures": [{ "offset": u64::MAX, "text": "P" }] },
+        { "name": "Empty", "extensions": [], "icon_file": "", "signatures": [{ "hex": "" }, {}] }
+    ]));
+    let registry = BundleRegistry::scan(vec![SearchRoot::new(SearchScope::User, root)]);
+    let file = dir.path.join("data");
+    fs::write(&file, b"\xc3\xa4PK\0").unwrap();
+
+    assert!(identify(&registry, &file).is_empty());
+}
@@ -0,0 +1,49 @@
+//! Eingebettete Bundles: Suche in den bekannten Verzeichnissen, fehlende
+//! Verzeichnisse und Kollisionen von Bezeichnern.
+
+mod common;
+
+use std::fs;
+
+use bundle::{Bundle, BundleKind};
+use common::{create_bundle_with, TempDir};
+
+#[test]
+fn embedded_bundles_are_enumerated_with_collisions() {
+    let dir = TempDir::new("embedded-bundles");
+    let app = create_bundle_with(&dir.path, BundleKind::App, "org.example.host", |_| {});
+    let content = app.join("Content");
+
+    let framework = create_bundle_with(&content.join("Frameworks"), BundleKind::Framework, "org.example.fw", |_| {});
+    // Im falschen Verzeichnis: wird übersprungen
+    create_bundle_with(&content.join("Frameworks"), BundleKind::Service, "org.example.misplaced", |_| {});
+    let plugin = create_bundle_with(&content.join("PlugIns"), BundleKind::Service, "org.example.fw", |_| {});
+    let shadow = create_bundle_with(&content.join("PlugIns"), BundleKind::Toolset, "org.example.host", |_| {});
+    let broken = content.join("PlugIns/org.example.broken.frameworkd");
+    fs::create_dir_all(broken.join("Content")).unwrap();
+    fs::write(broken.join("Content/Config.json"), "{}").unwrap();
+    fs::write(broken.join("Content/Info.json"), "{").unwrap();
+    // Kein Verzeichnis: gilt wie ein fehlendes als leer
+    fs::write(content.join("Toolsets"), "").unwrap();
+
+    let embedded = Bundle::open(&app).unwrap().embedded_bundles().unwrap();
+
+    let paths: Vec<_> = embedded.bundles.iter().map(|b| b.path().to_path_buf()).collect();
+    assert_eq!(paths, [framework.clone(), plugin.clone(), shadow.clone()]);
+    assert_eq!(embedded.errors.len(), 1, "{:?}", embedded.errors);
+    assert_eq!(embedded.errors[0].0, broken);
+
+    let collisions: Vec<_> = embedded.collisions.iter().map(|c| (c.identifier.as_str(), c.paths.clone())).collect();
+    assert_eq!(collisions, [("org.example.fw", vec![framework, plugin]), ("org.example.host", vec![app, shadow])]);
+}
+
+#[test]
+fn bundle_without_embedding_directories_is_empty() {
+    let dir = TempDir::new("embedded-bundles-none");
+    let app = create_bundle_with(&dir.path, BundleKind::App, "org.example.alone", |_| {});
+
+    let embedded = Bundle::open(&app).unwrap().embedded_bundles().unwrap();
+    assert!(embedded.bundles.is_empty());
+    assert!(embedded.errors.is_empty());
+    assert!(embedded.collisions.is_empty());
+}
@@ -0,0 +1,31 @@
+//! Jede Variante von `EntitlementType` muss über alle String-Wege verlustfrei
+//! hin- und zurückkommen.
+
+use std::collections::HashSet;
+
+use bundle::EntitlementType;
+
+#[test]
+fn every_variant_round_trips() {
+    for &entitlement in EntitlementType::all() {
+        let name = entitlement.as_str();
+        assert_eq!(entitlement.to_string(), name);
+        assert_eq!(name.parse::<EntitlementType>().unwrap(), entitlement);
+
+        let json = serde_json::to_string(&entitlement).unwrap();
+        assert_eq!(json, format!("\"{}\"", name));
+        assert_eq!(serde_json::from_str::<EntitlementType>(&json).unwrap(), entitlement);
+    }
+}
+
+#[test]
+fn names_are_unique() {
+    let names: HashSet<&str> = EntitlementType::all().iter().map(EntitlementType::as_str).collect();
+    assert_eq!(names.len(), EntitlementType::all().len());
+}
+
+#[test]
+fn unknown_names_are_rejected() {
+    assert!("teleportation".parse::<EntitlementType>().is_err());
+    assert!(serde_json::from_str::<EntitlementType>("\"Camera\"").is_err());
+}
@@ -0,0 +1,53 @@
+//! Executables und `entry_point` dürfen `Content/` weder über `..` noch über
+//! absolute Pfade verlassen.
+
+mod common;
+
+use bundle::{Bundle, BundleError, BundleKind, FrameworkBundle};
+use common::{create_bundle_with, TempDir};
+use serde_json::json;
+
+fn bundle_with_executable(dir: &TempDir, path: &str) -> Bundle {
+    let bundle = create_bundle_with(&dir.path, BundleKind::App, "org.example.tools", |info| {
+        info["executables"] = json!({ "tool": { "path": path } });
+    });
+    Bundle::open(&bundle).unwrap()
+}
+
+#[test]
+fn executable_inside_content_resolves() {
+    let dir = TempDir::new("executables-inside");
+    let bundle = bundle_with_executable(&dir, "bin/tool");
+    assert_eq!(bundle.executable("tool").unwrap().path, bundle.content_path().join("bin/tool"));
+}
+
+#[test]
+fn escaping_executable_paths_are_rejected() {
+    for path in ["../../../bin/sh", "/bin/sh", "bin/../../x"] {
+        let dir = TempDir::new("executables-escape");
+        let bundle = bundle_with_executable(&dir, path);
+        assert!(matches!(bundle.executable("tool"), Err(BundleError::InvalidFormat(_))), "{}", path);
+        assert!(matches!(bundle.executables(), Err(BundleError::InvalidFormat(_))), "{}", path);
+        assert!(matches!(bundle.launch_executable("tool", &[], &[]), Err(BundleError::InvalidFormat(_))), "{}", path);
+        assert!(!bundle.validate_executables().unwrap().is_valid);
+    }
+}
+
+#[test]
+fn escaping_entry_points_are_rejected() {
+    for entry_point in ["../../../bin/sh", "/bin/sh", "bin/../../x", ""] {
+        let dir = TempDir::new("executables-entry-point");
+        let app = create_bundle_with(&dir.path, BundleKind::App, "org.example.app", |info| {
+            info["entry_point"] = json!(entry_point);
+        });
+        let bundle = Bundle::open(&app).unwrap();
+        assert!(matches!(bundle.executable_path(), Err(BundleError::InvalidFormat(_))), "{:?}", entry_point);
+        assert!(matches!(bundle.launch(&[], &[]), Err(BundleError::InvalidFormat(_))), "{:?}", entry_point);
+
+        let framework = create_bundle_with(&dir.path, BundleKind::Framework, "org.example.fw", |info| {
+            info["entry_point"] = json!(entry_point);
+        });
+        let framework = FrameworkBundle::open(&framework).unwrap();
+        assert!(matches!(framework.load(), Err(BundleError::InvalidFormat(_))), "{:?}", entry_point);
+    }
+}
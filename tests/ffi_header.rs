@@ -0,0 +1,35 @@
+//! Stellt sicher, dass `include/bundle.h` zu den exportierten Symbolen passt.
+//!
+//! Nach Änderungen an der C-Schnittstelle neu erzeugen mit:
+//! `BUNDLE_UPDATE_HEADER=1 cargo test --test ffi_header`
+
+use std::path::Path;
+
+#[test]
+fn header_matches_exported_symbols() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let header_path = crate_dir.join("include/bundle.h");
+
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml konnte nicht gelesen werden");
+    let mut generated = Vec::new();
+    // Nur das FFI-Modul einlesen, damit Rust-seitige Typen nicht im Header landen
+    cbindgen::Builder::new()
+        .with_src(crate_dir.join("src/ffi/mod.rs"))
+        .with_config(config)
+        .generate()
+        .expect("Header konnte nicht erzeugt werden")
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).expect("Header ist kein UTF-8");
+
+    if std::env::var_os("BUNDLE_UPDATE_HEADER").is_some() {
+        std::fs::write(&header_path, &generated).expect("Header konnte nicht geschrieben werden");
+        return;
+    }
+
+    let committed = std::fs::read_to_string(&header_path).unwrap_or_default();
+    assert!(
+        committed == generated,
+        "include/bundle.h ist veraltet; mit BUNDLE_UPDATE_HEADER=1 neu erzeugen"
+    );
+}
@@ -0,0 +1,83 @@
+//! Iteratoren der C-Schnittstelle: Ende und Fehler sind über den letzten
+//! Fehlercode unterscheidbar.
+
+mod common;
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use bundle::ffi::*;
+use common::{create_bundle, TempDir};
+
+fn open(bundle: &std::path::Path) -> *mut BundleInfo {
+    let path = CString::new(bundle.as_os_str().as_encoded_bytes()).unwrap();
+    let info = unsafe { bundle_info_open(path.as_ptr()) };
+    assert!(!info.is_null());
+    info
+}
+
+fn take(s: *mut c_char) -> String {
+    let owned = unsafe { CStr::from_ptr(s) }.to_string_lossy().into_owned();
+    unsafe { bundle_string_free(s) };
+    owned
+}
+
+#[test]
+fn exhausted_iterator_leaves_no_error() {
+    let dir = TempDir::new("ffi-iter");
+    let bundle = dir.path.join("Iter.appd");
+    create_bundle(&bundle, "Iter", "org.example.iter");
+    let info = open(&bundle);
+
+    bundle_clear_last_error();
+    let iter = unsafe { bundle_info_metadata_iter(info) };
+    let (mut key, mut value) = (ptr::null_mut(), ptr::null_mut());
+    let mut entries = Vec::new();
+    while unsafe { bundle_metadata_iter_next(iter, &mut key, &mut value) } {
+        entries.push((take(key), take(value)));
+    }
+    assert_eq!(entries, vec![("category".to_string(), "test".to_string())]);
+    assert_eq!(bundle_last_error_code(), BundleErrorCode::Ok);
+
+    unsafe {
+        bundle_metadata_iter_free(iter);
+        bundle_info_free(info);
+    }
+}
+
+#[test]
+fn null_iterator_reports_invalid_argument() {
+    bundle_clear_last_error();
+    let (mut key, mut value) = (ptr::null_mut(), ptr::null_mut());
+    assert!(!unsafe { bundle_metadata_iter_next(ptr::null_mut(), &mut key, &mut value) });
+    assert_eq!(bundle_last_error_code(), BundleErrorCode::InvalidArgument);
+
+    bundle_clear_last_error();
+    assert!(unsafe { bundle_info_metadata_iter(ptr::null()) }.is_null());
+    assert_eq!(bundle_last_error_code(), BundleErrorCode::InvalidArgument);
+}
+
+#[test]
+fn entry_with_nul_byte_is_an_error() {
+    let dir = TempDir::new("ffi-iter-nul");
+    let bundle = dir.path.join("Nul.appd");
+    create_bundle(&bundle, "Nul", "org.example.nul");
+    let info_path = bundle.join("Content/Info.json");
+    let text = std::fs::read_to_string(&info_path).unwrap().replace(r#""category": "test""#, r#""a": "x\u0000y", "b": "ok""#);
+    std::fs::write(&info_path, text).unwrap();
+    let info = open(&bundle);
+
+    let iter = unsafe { bundle_info_metadata_iter(info) };
+    let (mut key, mut value) = (ptr::null_mut(), ptr::null_mut());
+    bundle_clear_last_error();
+    assert!(!unsafe { bundle_metadata_iter_next(iter, &mut key, &mut value) });
+    assert_eq!(bundle_last_error_code(), BundleErrorCode::InvalidFormat);
+    // Der fehlerhafte Eintrag ist übersprungen, der nächste folgt
+    assert!(unsafe { bundle_metadata_iter_next(iter, &mut key, &mut value) });
+    assert_eq!((take(key), take(value)), ("b".to_string(), "ok".to_string()));
+
+    unsafe {
+        bundle_metadata_iter_free(iter);
+        bundle_info_free(info);
+    }
+}
@@ -0,0 +1,126 @@
+//! Versionierte Frameworks: Auswahl der aktuellen Version, Prüfung des
+//! Aufbaus und Laden des Binaries per `dlopen`.
+
+mod common;
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+
+use bundle::{BundleError, BundleKind, FrameworkBundle};
+use common::{create_bundle_with, write_executable, TempDir};
+use serde_json::json;
+
+/// Framework mit den Versionen `1` und `2`, `Current -> 2` und
+/// `Content -> Versions/Current/Content`.
+fn versioned_framework(dir: &TempDir) -> PathBuf {
+    let mut framework = PathBuf::new();
+    for version in ["1", "2"] {
+        framework = create_bundle_with(&dir.path, BundleKind::Framework, "org.example.fw", |info| {
+            info["entry_point"] = json!(format!("lib/libfw.so.{}", version));
+        });
+        let target = framework.join("Versions").join(version);
+        fs::create_dir_all(&target).unwrap();
+        fs::rename(framework.join("Content"), target.join("Content")).unwrap();
+    }
+    symlink("2", framework.join("Versions/Current")).unwrap();
+    symlink("Versions/Current/Content", framework.join("Content")).unwrap();
+    framework
+}
+
+/// Pfad der libc, mit der dieser Testprozess läuft.
+fn host_shared_library() -> PathBuf {
+    let maps = fs::read_to_string("/proc/self/maps").unwrap();
+    maps.lines()
+        .filter_map(|line| line.split_whitespace().nth(5))
+        .find(|path| Path::new(path).file_name().is_some_and(|name| name.to_string_lossy().starts_with("libc.so")))
+        .map(PathBuf::from)
+        .expect("libc ist gemappt")
+}
+
+#[test]
+fn current_version_is_selected() {
+    let dir = TempDir::new("framework-versions");
+    let framework = FrameworkBundle::open(versioned_framework(&dir)).unwrap();
+
+    assert!(framework.is_versioned());
+    let names: Vec<String> = framework.versions().unwrap().into_iter().map(|v| v.name).collect();
+    assert_eq!(names, ["1", "2"]);
+    assert_eq!(framework.current_version().unwrap().unwrap().name, "2");
+    assert!(framework.executable_path().unwrap().ends_with("Content/lib/libfw.so.2"));
+    assert!(framework.validate_versions().is_valid, "{}", framework.validate_versions().message);
+}
+
+#[test]
+fn unversioned_framework_has_no_versions() {
+    let dir = TempDir::new("framework-plain");
+    let path = create_bundle_with(&dir.path, BundleKind::Framework, "org.example.plain", |_| {});
+    let framework = FrameworkBundle::open(path).unwrap();
+
+    assert!(!framework.is_versioned());
+    assert!(framework.versions().unwrap().is_empty());
+    assert!(framework.current_version().unwrap().is_none());
+}
+
+#[test]
+fn other_bundle_kinds_are_rejected() {
+    let dir = TempDir::new("framework-kind");
+    let path = create_bundle_with(&dir.path, BundleKind::App, "org.example.app", |_| {});
+    assert!(matches!(FrameworkBundle::open(path), Err(BundleError::InvalidFormat(_))));
+}
+
+#[test]
+fn invalid_current_links_are_reported() {
+    let dir = TempDir::new("framework-current");
+    let path = versioned_framework(&dir);
+    let current = path.join("Versions/Current");
+
+    fs::remove_file(&current).unwrap();
+    symlink("../Versions/1", &current).unwrap();
+    let framework = FrameworkBundle::open(&path).unwrap();
+    assert!(matches!(framework.current_version(), Err(BundleError::InvalidFormat(_))));
+    assert!(!framework.validate_versions().is_valid);
+
+    fs::remove_file(&current).unwrap();
+    symlink("3", &current).unwrap();
+    assert!(matches!(framework.current_version(), Err(BundleError::NotFound(_))));
+}
+
+#[test]
+fn symlinks_out_of_the_bundle_are_invalid() {
+    let dir = TempDir::new("framework-escape");
+    let path = versioned_framework(&dir);
+    symlink(&dir.path, path.join("Versions/2/Content/outside")).unwrap();
+
+    let result = FrameworkBundle::open(&path).unwrap().validate_versions();
+    assert!(!result.is_valid);
+    assert!(result.message.contains("outside the bundle"), "{}", result.message);
+}
+
+#[test]
+fn current_binary_is_loaded() {
+    let dir = TempDir::new("framework-load");
+    let path = versioned_framework(&dir);
+    let binary = path.join("Versions/2/Content/lib/libfw.so.2");
+    fs::create_dir_all(binary.parent().unwrap()).unwrap();
+    fs::copy(host_shared_library(), &binary).unwrap();
+
+    let framework = FrameworkBundle::open(&path).unwrap();
+    let loaded = framework.load().unwrap();
+    assert_eq!(loaded.path(), framework.executable_path().unwrap());
+
+    type Strlen = unsafe extern "C" fn(*const std::ffi::c_char) -> usize;
+    let strlen = unsafe { loaded.symbol::<Strlen>("strlen") }.unwrap();
+    assert_eq!(unsafe { strlen(c"framework".as_ptr()) }, 9);
+    assert!(matches!(unsafe { loaded.symbol::<Strlen>("no_such_symbol") }, Err(BundleError::NotFound(_))));
+}
+
+#[test]
+fn missing_or_foreign_binaries_are_not_loaded() {
+    let dir = TempDir::new("framework-load-errors");
+    let framework = FrameworkBundle::open(versioned_framework(&dir)).unwrap();
+    assert!(matches!(framework.load(), Err(BundleError::NotFound(_))));
+
+    write_executable(&framework.executable_path().unwrap(), "#!/bin/sh\n");
+    assert!(matches!(framework.load(), Err(BundleError::InvalidFormat(_))));
+}
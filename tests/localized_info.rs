@@ -0,0 +1,77 @@
+//! Lokalisierte Info.json-Werte: Fallback-Kette über regionale Variante,
+//! reine Sprache und Entwicklungssprache.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use bundle::{Bundle, BundleError, BundleKind};
+use common::{create_bundle_with, TempDir};
+use serde_json::{json, Value};
+
+fn write_overrides(bundle: &Path, language: &str, overrides: Value) {
+    let dir = bundle.join(format!("Content/Resources/{}.lproj", language));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("Info.json"), overrides.to_string()).unwrap();
+}
+
+fn localized_bundle(dir: &TempDir) -> Bundle {
+    let path = create_bundle_with(&dir.path, BundleKind::App, "org.example.localized", |info| {
+        info["name"] = json!("Base");
+        info["development_language"] = json!("en");
+        info["url_schemes"] = json!([
+            { "scheme": "demo", "description": "Demo links" },
+            { "scheme": "other", "description": "Other links" }
+        ]);
+        info["fibyos"]["document_types"] = json!([
+            { "name": "Text", "extensions": ["txt"], "icon_file": "" },
+            { "name": "Image", "extensions": ["png"], "icon_file": "" }
+        ]);
+    });
+    write_overrides(&path, "en", json!({ "name": "English", "document_types": { "Image": "Picture" } }));
+    write_overrides(&path, "de", json!({
+        "name": "Deutsch",
+        "url_schemes": { "demo": "Beispiel-Links" },
+        "document_types": { "Text": "Textdokument" }
+    }));
+    write_overrides(&path, "de_AT", json!({ "name": "Österreichisch" }));
+    Bundle::open(&path).unwrap()
+}
+
+#[test]
+fn first_language_in_chain_wins_per_value() {
+    let dir = TempDir::new("localized-info-chain");
+    let bundle = localized_bundle(&dir);
+
+    let info = bundle.localized_info(Some("de-AT")).unwrap();
+    assert_eq!(info.name, "Österreichisch");
+    let schemes: Vec<&str> = info.url_schemes.iter().map(|u| u.description.as_str()).collect();
+    assert_eq!(schemes, ["Beispiel-Links", "Other links"]);
+    let types: Vec<&str> = info.fibyos.document_types.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(types, ["Textdokument", "Picture"]);
+}
+
+#[test]
+fn unknown_language_falls_back_to_development_language() {
+    let dir = TempDir::new("localized-info-fallback");
+    let bundle = localized_bundle(&dir);
+
+    let info = bundle.localized_info(Some("fr")).unwrap();
+    assert_eq!(info.name, "English");
+    assert_eq!(info.url_schemes[0].description, "Demo links");
+    assert_eq!(info.fibyos.document_types[0].name, "Text");
+    assert_eq!(info.fibyos.document_types[1].name, "Picture");
+    // Die unlokalisierte Info.json bleibt unverändert
+    assert_eq!(bundle.info().unwrap().name, "Base");
+}
+
+#[test]
+fn malformed_overrides_are_reported() {
+    let dir = TempDir::new("localized-info-malformed");
+    let bundle = localized_bundle(&dir);
+    fs::write(bundle.resources_path().join("de.lproj/Info.json"), "{ not json").unwrap();
+
+    assert!(bundle.localized_info_overrides("fr").unwrap().is_none());
+    assert!(matches!(bundle.localized_info(Some("de")), Err(BundleError::InvalidFormat(_))));
+}
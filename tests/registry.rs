@@ -0,0 +1,75 @@
+//! Bundle-Registry: Verdeckung über Suchstufen und Abfragen nach Art und Team.
+
+mod common;
+
+use std::fs;
+
+use bundle::{BundleKind, BundleRegistry, RegisteredBundle, SearchRoot, SearchScope};
+use common::{create_bundle_with, TempDir};
+use serde_json::json;
+
+fn team(team_id: &str) -> impl FnOnce(&mut serde_json::Value) + '_ {
+    move |info| info["security"]["code_signature"]["team_id"] = json!(team_id)
+}
+
+#[test]
+fn higher_scope_shadows_lower_scope() {
+    let dir = TempDir::new("registry-shadow");
+    let system = dir.path.join("system");
+    let user = dir.path.join("user");
+    let shadowed = create_bundle_with(&system, BundleKind::App, "org.example.editor", |_| {});
+    let effective = create_bundle_with(&user, BundleKind::App, "org.example.editor", |_| {});
+    let tool = create_bundle_with(&system, BundleKind::Toolset, "org.example.tool", |_| {});
+
+    // Reihenfolge der Wurzeln spielt für den Vorrang keine Rolle
+    let registry = BundleRegistry::scan(vec![
+        SearchRoot::new(SearchScope::User, &user),
+        SearchRoot::new(SearchScope::System, &system),
+    ]);
+
+    assert_eq!(registry.get("org.example.editor").unwrap().path, effective);
+    let all: Vec<_> = registry.all_with_identifier("org.example.editor").into_iter().map(|b| b.path.clone()).collect();
+    assert_eq!(all, [effective.clone(), shadowed]);
+    let visible: Vec<_> = registry.bundles().map(|b| b.path.clone()).collect();
+    assert_eq!(visible, [effective, tool]);
+    assert!(registry.get("org.example.missing").is_none());
+    assert!(registry.all_with_identifier("org.example.missing").is_empty());
+}
+
+#[test]
+fn bundles_are_filtered_by_kind_and_team() {
+    let dir = TempDir::new("registry-filter");
+    let root = dir.path.join("bundles");
+    create_bundle_with(&root, BundleKind::App, "org.example.a", team("A"));
+    create_bundle_with(&root, BundleKind::Service, "org.example.b", team("A"));
+    create_bundle_with(&root, BundleKind::Service, "org.example.c", team("B"));
+
+    let registry = BundleRegistry::scan(vec![SearchRoot::new(SearchScope::Local, &root)]);
+    let identifiers = |bundles: Vec<&RegisteredBundle>| -> Vec<String> {
+        bundles.into_iter().map(|b| b.identifier().to_string()).collect()
+    };
+
+    assert_eq!(identifiers(registry.bundles_of_kind(BundleKind::Service).collect()), ["org.example.b", "org.example.c"]);
+    assert_eq!(identifiers(registry.bundles_for_team("A").collect()), ["org.example.a", "org.example.b"]);
+    assert!(registry.bundles_of_kind(BundleKind::Framework).next().is_none());
+}
+
+#[test]
+fn unreadable_bundles_are_reported_and_other_entries_ignored() {
+    let dir = TempDir::new("registry-errors");
+    let root = dir.path.join("bundles");
+    create_bundle_with(&root, BundleKind::App, "org.example.good", |_| {});
+    let broken = root.join("Broken.appd");
+    fs::create_dir_all(broken.join("Content")).unwrap();
+    fs::write(broken.join("Content/Config.json"), "{}").unwrap();
+    fs::write(broken.join("Content/Info.json"), "{").unwrap();
+    fs::write(root.join("notes.txt"), "").unwrap();
+
+    let registry = BundleRegistry::scan(vec![
+        SearchRoot::new(SearchScope::User, &root),
+        SearchRoot::new(SearchScope::System, dir.path.join("missing")),
+    ]);
+    assert_eq!(registry.bundles().count(), 1);
+    assert_eq!(registry.errors().len(), 1);
+    assert_eq!(registry.errors()[0].0, broken);
+}
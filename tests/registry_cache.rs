@@ -0,0 +1,92 @@
+//! Registry-Cache: Round-Trip, Invalidierung über den Stempel der Info.json
+//! und Rückfall auf einen leeren Cache bei fremder Schema-Version.
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bundle::{BundleRegistry, SearchRoot, SearchScope, REGISTRY_CACHE_SCHEMA_VERSION};
+use common::{create_bundle, TempDir};
+use serde_json::Value;
+
+const IDENTIFIER: &str = "org.example.cache";
+
+struct Fixture {
+    dir: TempDir,
+}
+
+impl Fixture {
+    fn new(name: &str) -> Fixture {
+        let fixture = Fixture { dir: TempDir::new(name) };
+        fixture.write_info("Original");
+        fixture
+    }
+
+    fn write_info(&self, name: &str) {
+        create_bundle(&self.dir.path.join("bundles/Cache.appd"), name, IDENTIFIER);
+    }
+
+    fn cache(&self) -> PathBuf {
+        self.dir.path.join("registry.json")
+    }
+
+    fn scan(&self) -> BundleRegistry {
+        let roots = vec![SearchRoot::new(SearchScope::User, self.dir.path.join("bundles"))];
+        BundleRegistry::scan_cached(roots, self.cache())
+    }
+}
+
+fn name(registry: &BundleRegistry) -> String {
+    registry.get(IDENTIFIER).expect("bundle registriert").info.name.clone()
+}
+
+/// Ersetzt den Namen im einzigen Cache-Eintrag und optional die Schema-Version.
+fn tamper_cache(path: &Path, name: &str, schema: Option<u32>) {
+    let mut file: Value = serde_json::from_slice(&fs::read(path).unwrap()).unwrap();
+    let entries = file["entries"].as_object_mut().unwrap();
+    assert_eq!(entries.len(), 1);
+    for entry in entries.values_mut() {
+        entry["info"]["name"] = Value::from(name);
+    }
+    if let Some(schema) = schema {
+        file["schema"] = Value::from(schema);
+    }
+    fs::write(path, serde_json::to_vec(&file).unwrap()).unwrap();
+}
+
+#[test]
+fn unchanged_info_file_is_served_from_cache() {
+    let fixture = Fixture::new("cache-hit");
+    assert_eq!(name(&fixture.scan()), "Original");
+    assert!(fixture.cache().is_file());
+
+    tamper_cache(&fixture.cache(), "FromCache", None);
+    assert_eq!(name(&fixture.scan()), "FromCache");
+}
+
+#[test]
+fn touched_info_file_is_read_again() {
+    let fixture = Fixture::new("cache-stale");
+    assert_eq!(name(&fixture.scan()), "Original");
+
+    tamper_cache(&fixture.cache(), "FromCache", None);
+    fixture.write_info("Updated name");
+    assert_eq!(name(&fixture.scan()), "Updated name");
+
+    // Der neu gelesene Eintrag ersetzt den alten im Cache
+    tamper_cache(&fixture.cache(), "FromCache", None);
+    assert_eq!(name(&fixture.scan()), "FromCache");
+}
+
+#[test]
+fn foreign_schema_version_is_ignored() {
+    let fixture = Fixture::new("cache-schema");
+    assert_eq!(name(&fixture.scan()), "Original");
+
+    tamper_cache(&fixture.cache(), "FromCache", Some(REGISTRY_CACHE_SCHEMA_VERSION + 1));
+    assert_eq!(name(&fixture.scan()), "Original");
+
+    let file: Value = serde_json::from_slice(&fs::read(fixture.cache()).unwrap()).unwrap();
+    assert_eq!(file["schema"], REGISTRY_CACHE_SCHEMA_VERSION);
+}
@@ -0,0 +1,87 @@
+//! Bundle-Watcher: mehrere Dateisystem-Ereignisse eines Bundles werden
+//! innerhalb der Wartezeit zu einer einzigen Meldung zusammengefasst, auch
+//! wenn sie tief im Bundle stattfinden.
+
+mod common;
+
+use std::fs;
+use std::time::{Duration, Instant};
+
+use bundle::{BundleEvent, BundleWatcher, SearchRoot, SearchScope};
+use common::{create_bundle, info_json, TempDir};
+
+const DEBOUNCE: Duration = Duration::from_millis(200);
+const TIMEOUT: Duration = Duration::from_secs(5);
+const IDENTIFIER: &str = "org.example.watched";
+
+#[test]
+fn install_change_and_removal_are_coalesced() {
+    let dir = TempDir::new("watcher");
+    let root = dir.path.join("bundles");
+    fs::create_dir_all(&root).unwrap();
+    let bundle = root.join("Watched.appd");
+    let mut watcher = BundleWatcher::new(&[SearchRoot::new(SearchScope::User, &root)], DEBOUNCE).unwrap();
+
+    // Schrittweise Installation: Verzeichnisse, Config.json, Info.json in zwei Schreibvorgängen
+    let info = info_json("Watched", IDENTIFIER);
+    let (head, tail) = info.split_at(info.len() / 2);
+    fs::create_dir_all(bundle.join("Content")).unwrap();
+    fs::write(bundle.join("Content/Config.json"), "{}").unwrap();
+    fs::write(bundle.join("Content/Info.json"), head).unwrap();
+    fs::write(bundle.join("Content/Info.json"), [head, tail].concat()).unwrap();
+
+    let events = watcher.poll(Some(TIMEOUT)).unwrap();
+    assert_eq!(events, vec![BundleEvent::BundleAdded { identifier: IDENTIFIER.into(), path: bundle.clone() }]);
+    assert_eq!(watcher.poll(Some(DEBOUNCE * 2)).unwrap(), vec![]);
+
+    create_bundle(&bundle, "Renamed", IDENTIFIER);
+    create_bundle(&bundle, "Renamed again", IDENTIFIER);
+    let events = watcher.poll(Some(TIMEOUT)).unwrap();
+    assert_eq!(events, vec![BundleEvent::InfoChanged { identifier: IDENTIFIER.into(), path: bundle.clone() }]);
+
+    fs::remove_dir_all(&bundle).unwrap();
+    let events = watcher.poll(Some(TIMEOUT)).unwrap();
+    assert_eq!(events, vec![BundleEvent::BundleRemoved { identifier: IDENTIFIER.into(), path: bundle.clone() }]);
+    assert_eq!(watcher.poll(Some(DEBOUNCE * 2)).unwrap(), vec![]);
+}
+
+#[test]
+fn existing_bundles_are_not_reported() {
+    let dir = TempDir::new("watcher-existing");
+    let root = dir.path.join("bundles");
+    create_bundle(&root.join("Existing.appd"), "Existing", IDENTIFIER);
+
+    let mut watcher = BundleWatcher::new(&[SearchRoot::new(SearchScope::User, &root)], DEBOUNCE).unwrap();
+    assert_eq!(watcher.poll(Some(DEBOUNCE * 2)).unwrap(), vec![]);
+}
+
+#[test]
+fn deep_writes_delay_bundle_added() {
+    let dir = TempDir::new("watcher-deep");
+    let root = dir.path.join("bundles");
+    fs::create_dir_all(&root).unwrap();
+    let bundle = root.join("Deep.appd");
+    let mut watcher = BundleWatcher::new(&[SearchRoot::new(SearchScope::User, &root)], DEBOUNCE).unwrap();
+
+    // Info.json ist sofort gültig, die Kopie läuft aber noch tief im Bundle weiter
+    let writer = {
+        let bundle = bundle.clone();
+        std::thread::spawn(move || {
+            create_bundle(&bundle, "Deep", IDENTIFIER);
+            let deep = bundle.join("Content/lib/deep");
+            fs::create_dir_all(&deep).unwrap();
+            for i in 0..6 {
+                std::thread::sleep(DEBOUNCE / 2);
+                fs::write(deep.join(format!("part{}", i)), "data").unwrap();
+            }
+            Instant::now()
+        })
+    };
+
+    let events = watcher.poll(Some(TIMEOUT)).unwrap();
+    let reported = Instant::now();
+    let last_write = writer.join().unwrap();
+    assert_eq!(events, vec![BundleEvent::BundleAdded { identifier: IDENTIFIER.into(), path: bundle.clone() }]);
+    assert!(reported >= last_write + DEBOUNCE, "reported {:?} before the last write", last_write + DEBOUNCE - reported);
+    assert_eq!(watcher.poll(Some(DEBOUNCE * 2)).unwrap(), vec![]);
+}
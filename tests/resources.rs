@@ -0,0 +1,76 @@
+//! Ressourcensuche: lokalisierte Varianten vor der Basisversion, keine
+//! Auflösung außerhalb von `Resources/`.
+
+mod common;
+
+use std::fs;
+
+use bundle::{Bundle, BundleKind};
+use common::{create_bundle_with, TempDir};
+use serde_json::json;
+
+/// Bundle mit Entwicklungssprache `xx`, damit die Sprachkette unabhängig von
+/// der Umgebung des Tests `xx.lproj` enthält.
+fn resource_bundle(dir: &TempDir) -> Bundle {
+    let path = create_bundle_with(&dir.path, BundleKind::App, "org.example.resources", |info| {
+        info["development_language"] = json!("xx");
+    });
+    let resources = path.join("Content/Resources");
+    for (file, content) in [
+        ("greeting.txt", "base"),
+        ("only-base.txt", "base"),
+        ("xx.lproj/greeting.txt", "localized"),
+        ("sounds/beep.wav", ""),
+        ("sounds/boop.wav", ""),
+        ("xx.lproj/sounds/beep.wav", ""),
+    ] {
+        let file = resources.join(file);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, content).unwrap();
+    }
+    // Außerhalb von Resources/, darf nie gefunden werden
+    fs::write(path.join("Content/secret.txt"), "").unwrap();
+    Bundle::open(&path).unwrap()
+}
+
+#[test]
+fn localized_resource_wins_over_base() {
+    let dir = TempDir::new("resources-lookup");
+    let bundle = resource_bundle(&dir);
+    let resources = bundle.resources_path();
+
+    assert_eq!(bundle.path_for_resource("greeting", Some("txt"), None), Some(resources.join("xx.lproj/greeting.txt")));
+    assert_eq!(bundle.path_for_resource("only-base.txt", None, None), Some(resources.join("only-base.txt")));
+    assert_eq!(bundle.path_for_resource("missing", Some("txt"), None), None);
+    assert_eq!(
+        bundle.path_for_resource("boop", Some("wav"), Some("sounds")),
+        Some(resources.join("sounds/boop.wav"))
+    );
+    assert_eq!(
+        bundle.paths_for_resources_of_type("wav", Some("sounds")),
+        vec![resources.join("xx.lproj/sounds/beep.wav"), resources.join("sounds/boop.wav")]
+    );
+}
+
+#[test]
+fn escaping_names_are_not_resolved() {
+    let dir = TempDir::new("resources-escape");
+    let bundle = resource_bundle(&dir);
+
+    for name in ["../secret.txt", "sounds/../../secret.txt", "/etc/passwd", "", "./greeting.txt"] {
+        assert_eq!(bundle.path_for_resource(name, None, None), None, "{:?}", name);
+    }
+    assert_eq!(bundle.path_for_resource("../secret", Some("txt"), None), None);
+}
+
+#[test]
+fn escaping_subdirectories_are_not_searched() {
+    let dir = TempDir::new("resources-escape-subdir");
+    let bundle = resource_bundle(&dir);
+
+    for subdirectory in ["..", "../..", "/etc", "sounds/../..", ""] {
+        assert!(bundle.resource_search_paths(Some(subdirectory)).is_empty(), "{:?}", subdirectory);
+        assert_eq!(bundle.path_for_resource("secret", Some("txt"), Some(subdirectory)), None);
+        assert!(bundle.paths_for_resources_of_type("txt", Some(subdirectory)).is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+//! Abhängigkeiten zwischen installierten Diensten: Zyklen über `after` und
+//! `requires` werden gemeldet, eine Raute (gemeinsame Abhängigkeit) nicht.
+//! Gesundheitsprüfungen laufen nur mit Programmen innerhalb von `Content/`.
+
+mod common;
+
+use bundle::{Bundle, BundleError, BundleKind, BundleRegistry, SearchRoot, SearchScope};
+use common::{create_bundle_with, write_executable, TempDir};
+use serde_json::{json, Value};
+
+fn install_service(dir: &TempDir, identifier: &str, service: Value) {
+    create_bundle_with(&dir.path, BundleKind::Service, identifier, |info| info["service"] = service);
+}
+
+fn registry(dir: &TempDir) -> BundleRegistry {
+    BundleRegistry::scan(vec![SearchRoot::new(SearchScope::User, &dir.path)])
+}
+
+#[test]
+fn cycle_across_after_and_requires_is_reported() {
+    let dir = TempDir::new("services-cycle");
+    install_service(&dir, "org.example.a", json!({ "requires": ["org.example.b"] }));
+    install_service(&dir, "org.example.b", json!({ "after": ["org.example.c"] }));
+    install_service(&dir, "org.example.c", json!({ "requires": ["org.example.a"] }));
+
+    let result = registry(&dir).validate_services();
+    assert!(!result.is_valid);
+    assert_eq!(
+        result.message,
+        "dependency cycle: org.example.a -> org.example.b -> org.example.c -> org.example.a"
+    );
+}
+
+#[test]
+fn diamond_is_valid() {
+    let dir = TempDir::new("services-diamond");
+    install_service(&dir, "org.example.top", json!({ "requires": ["org.example.left", "org.example.right"] }));
+    install_service(&dir, "org.example.left", json!({ "after": ["org.example.bottom"] }));
+    install_service(&dir, "org.example.right", json!({ "requires": ["org.example.bottom"] }));
+    install_service(&dir, "org.example.bottom", json!({}));
+
+    let result = registry(&dir).validate_services();
+    assert!(result.is_valid, "{}", result.message);
+    assert!(result.warnings.is_empty(), "{:?}", result.warnings);
+}
+
+fn health_bundle(dir: &TempDir, command: &[&str]) -> Bundle {
+    let path = create_bundle_with(&dir.path, BundleKind::Service, "org.example.health", |info| {
+        info["service"] = json!({ "health_check": { "command": command, "timeout_seconds": 5 } });
+    });
+    write_executable(&path.join("Content/bin/check"), "#!/bin/sh\nexit \"$1\"\n");
+    Bundle::open(&path).unwrap()
+}
+
+#[test]
+fn health_check_runs_program_inside_content() {
+    let dir = TempDir::new("services-health");
+    assert!(health_bundle(&dir, &["bin/check", "0"]).check_health().unwrap());
+    assert!(!health_bundle(&dir, &["bin/check", "3"]).check_health().unwrap());
+    assert!(health_bundle(&dir, &["${BUNDLE_CONTENT_PATH}/bin/check", "0"]).check_health().unwrap());
+}
+
+#[test]
+fn health_check_program_must_stay_inside_content() {
+    let dir = TempDir::new("services-health-escape");
+    for program in ["../../../bin/true", "/bin/true", "${BUNDLE_PATH}/../x", "${BUNDLE_CONTENT_PATH}/../Info.json", "."] {
+        let result = health_bundle(&dir, &[program]).check_health();
+        assert!(matches!(result, Err(BundleError::InvalidFormat(_))), "{}: {:?}", program, result);
+    }
+}
@@ -0,0 +1,249 @@
+//! Socket-Aktivierung über den eigenen Starter und das LISTEN_FDS-Protokoll.
+//!
+//! [`listen_fds`] übernimmt die Deskriptoren 3.. des laufenden Prozesses;
+//! im Testprozess gehören diese dem Test-Harness. Die Prüfungen dazu laufen
+//! daher in einem Kindprozess: dieselbe Testdatei, gefiltert auf
+//! `child_reports_listen_sockets`, mit vorbereiteten Deskriptoren.
+
+mod common;
+
+use std::fs;
+use std::io::ErrorKind;
+use std::os::fd::{AsRawFd, OwnedFd};
+use std::os::unix::net::UnixListener;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+
+use bundle::{listen_fds, Bundle, BundleError, BundleKind, SystemdScope};
+use common::{create_bundle_with, isolate_xdg, write_executable, TempDir};
+use serde_json::{json, Value};
+
+const CHILD_TEST: &str = "child_reports_listen_sockets";
+const ENV_CHILD_MODE: &str = "BUNDLE_TEST_CHILD_MODE";
+const ENV_CHILD_BUNDLE: &str = "BUNDLE_TEST_CHILD_BUNDLE";
+
+/// Schreibt LISTEN_*-Variablen und Typ der Deskriptoren 3 und 4 nach `$1`.
+const SCRIPT: &str = r#"#!/bin/sh
+{
+    echo "fds=$LISTEN_FDS names=$LISTEN_FDNAMES"
+    [ "$LISTEN_PID" = "$$" ] && echo "pid=ok"
+    [ -S /proc/$$/fd/3 ] && echo "fd3=socket"
+    [ -S /proc/$$/fd/4 ] && echo "fd4=socket"
+    [ -e /proc/$$/fd/5 ] && echo "fd5=open"
+} > "$1"
+exit 0
+"#;
+
+/// Service-Bundle mit `sockets` und dem Skript als `entry_point`.
+fn socket_bundle(dir: &TempDir, sockets: Value) -> Bundle {
+    let bundle = create_bundle_with(&dir.path, BundleKind::Service, "org.example.sockets", |info| {
+        info["entry_point"] = json!("bin/report");
+        info["service"] = json!({ "sockets": sockets });
+    });
+    write_executable(&bundle.join("Content/bin/report"), SCRIPT);
+    Bundle::open(&bundle).unwrap()
+}
+
+fn unix_sockets(dir: &TempDir) -> Value {
+    json!([
+        { "name": "ctl", "path": dir.path.join("run/ctl.sock") },
+        { "name": "web", "path": dir.path.join("run/web.sock") }
+    ])
+}
+
+fn launch_and_report(bundle: &Bundle, dir: &TempDir) -> String {
+    let report = dir.path.join("report");
+    let _ = fs::remove_file(&report);
+    let status = bundle.launch(&[report.to_str().unwrap()], &[]).unwrap().wait().unwrap();
+    assert!(status.success());
+    fs::read_to_string(&report).unwrap()
+}
+
+#[test]
+fn launched_service_receives_sockets_from_fd_3() {
+    isolate_xdg();
+    let dir = TempDir::new("sockets-launch");
+    let bundle = socket_bundle(&dir, unix_sockets(&dir));
+
+    assert_eq!(launch_and_report(&bundle, &dir), "fds=2 names=ctl:web\npid=ok\nfd3=socket\nfd4=socket\n");
+    // Verwaiste Socket-Dateien des ersten Laufs stehen einem zweiten Start nicht im Weg
+    assert!(dir.path.join("run/ctl.sock").exists());
+    assert_eq!(launch_and_report(&bundle, &dir), "fds=2 names=ctl:web\npid=ok\nfd3=socket\nfd4=socket\n");
+}
+
+#[test]
+fn live_socket_is_not_replaced() {
+    isolate_xdg();
+    let dir = TempDir::new("sockets-live");
+    let bundle = socket_bundle(&dir, unix_sockets(&dir));
+    fs::create_dir_all(dir.path.join("run")).unwrap();
+    let _running = UnixListener::bind(dir.path.join("run/web.sock")).unwrap();
+
+    match bundle.launch(&[], &[]) {
+        Err(BundleError::IoError(e)) => assert_eq!(e.kind(), ErrorKind::AddrInUse, "{}", e),
+        other => panic!("expected AddrInUse, got {:?}", other),
+    }
+    assert!(std::os::unix::net::UnixStream::connect(dir.path.join("run/web.sock")).is_ok());
+}
+
+#[test]
+fn tcp_port_in_use_is_reported() {
+    isolate_xdg();
+    let dir = TempDir::new("sockets-tcp");
+    let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = taken.local_addr().unwrap().port();
+    let bundle = socket_bundle(&dir, json!([{ "name": "web", "port": port, "address": "127.0.0.1" }]));
+
+    match bundle.launch(&[], &[]) {
+        Err(BundleError::IoError(e)) => {
+            assert_eq!(e.kind(), ErrorKind::AddrInUse);
+            assert!(e.to_string().contains(&format!("127.0.0.1:{}", port)), "{}", e);
+        }
+        other => panic!("expected AddrInUse, got {:?}", other),
+    }
+}
+
+#[test]
+fn default_address_listens_on_ipv4_and_ipv6() {
+    isolate_xdg();
+    for loopback in ["127.0.0.1", "::1"] {
+        let dir = TempDir::new("sockets-dual-stack");
+        let taken = std::net::TcpListener::bind((loopback, 0)).unwrap();
+        let port = taken.local_addr().unwrap().port();
+        let bundle = socket_bundle(&dir, json!([{ "name": "web", "port": port }]));
+
+        // Ein belegter Port auf einer der beiden Familien verhindert den Start
+        match bundle.launch(&[], &[]) {
+            Err(BundleError::IoError(e)) => assert_eq!(e.kind(), ErrorKind::AddrInUse, "{}: {}", loopback, e),
+            other => panic!("{}: expected AddrInUse, got {:?}", loopback, other),
+        }
+    }
+}
+
+#[test]
+fn invalid_socket_names_are_rejected() {
+    isolate_xdg();
+    for name in ["../escape", "a/b", "a:b", ""] {
+        let dir = TempDir::new("sockets-names");
+        let bundle = socket_bundle(&dir, json!([{ "name": name, "path": dir.path.join("run/x.sock") }]));
+        assert!(matches!(bundle.launch(&[], &[]), Err(BundleError::InvalidFormat(_))), "{:?}", name);
+        assert!(!dir.path.join("run/x.sock").exists());
+
+        let units = dir.path.join("units");
+        let result = bundle.write_systemd_unit(&units, SystemdScope::User);
+        assert!(matches!(result, Err(BundleError::InvalidFormat(_))), "{:?}: {:?}", name, result);
+        assert!(!units.exists());
+    }
+}
+
+#[test]
+fn data_directory_socket_paths_are_rejected() {
+    isolate_xdg();
+    let dir = TempDir::new("sockets-data-dir");
+    let bundle = socket_bundle(&dir, json!([{ "name": "ctl", "path": "${BUNDLE_DATA_DIR}/ctl.sock" }]));
+    assert!(matches!(bundle.launch(&[], &[]), Err(BundleError::InvalidFormat(_))));
+}
+
+/// Startet diese Testdatei als Kindprozess mit zwei Sockets auf 3 und 4 und
+/// liefert die Zeile, die [`child_reports_listen_sockets`] ausgibt.
+fn run_child(mode: &str, env: &[(&str, &str)], bundle: Option<&Path>) -> String {
+    let dir = TempDir::new(&format!("sockets-child-{}", mode));
+    let sockets: Vec<OwnedFd> = ["a.sock", "b.sock"]
+        .iter()
+        .map(|name| UnixListener::bind(dir.path.join(name)).unwrap().into())
+        .collect();
+    let raw: Vec<i32> = sockets.iter().map(|fd| fd.as_raw_fd()).collect();
+
+    let mut command = Command::new(std::env::current_exe().unwrap());
+    command
+        .args([CHILD_TEST, "--exact", "--nocapture", "--test-threads=1"])
+        .env(ENV_CHILD_MODE, mode)
+        .env_remove("LISTEN_FDNAMES");
+    for (key, value) in env {
+        command.env(key, value);
+    }
+    if let Some(bundle) = bundle {
+        command.env(ENV_CHILD_BUNDLE, bundle);
+    }
+    unsafe {
+        command.pre_exec(move || {
+            // Erst über die Zielnummern heben, damit dup2 keine Quelle überschreibt
+            let high: Vec<i32> = raw.iter().map(|&fd| libc::fcntl(fd, libc::F_DUPFD, 100)).collect();
+            for (index, &fd) in high.iter().enumerate() {
+                if fd < 0 || libc::dup2(fd, 3 + index as i32) < 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        });
+    }
+    let output = command.output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("RESULT "))
+        .unwrap_or_else(|| panic!("child printed no result:\n{}\n{}", stdout, String::from_utf8_lossy(&output.stderr)))
+        .to_string()
+}
+
+/// Läuft nur als Kindprozess von [`run_child`], sonst sofort fertig.
+#[test]
+fn child_reports_listen_sockets() {
+    let Ok(mode) = std::env::var(ENV_CHILD_MODE) else {
+        return;
+    };
+    if std::env::var("LISTEN_PID").as_deref() == Ok("self") {
+        unsafe { std::env::set_var("LISTEN_PID", std::process::id().to_string()) };
+    }
+    let result = match mode.as_str() {
+        "fds" => listen_fds().map(|sockets| {
+            sockets.into_vec().into_iter().map(|(name, fd)| format!("{}={}", name, fd.as_raw_fd())).collect::<Vec<_>>()
+        }),
+        "bundle" => Bundle::open(std::env::var(ENV_CHILD_BUNDLE).unwrap())
+            .and_then(|bundle| bundle.listen_sockets())
+            .map(|sockets| sockets.into_vec().into_iter().map(|(name, fd)| format!("{}={}", name, fd.as_raw_fd())).collect()),
+        _ => unreachable!(),
+    };
+    match result {
+        // Eigene Zeile: libtest schreibt den Testnamen ohne Zeilenumbruch davor
+        Ok(sockets) => println!("\nRESULT {}", sockets.join(" ")),
+        Err(e) => println!("\nRESULT error: {:?}", e),
+    }
+}
+
+#[test]
+fn listen_fds_keeps_names_positional() {
+    let env = [("LISTEN_PID", "self"), ("LISTEN_FDS", "2"), ("LISTEN_FDNAMES", ":web")];
+    assert_eq!(run_child("fds", &env, None), "unknown=3 web=4");
+
+    let env = [("LISTEN_PID", "self"), ("LISTEN_FDS", "2")];
+    assert_eq!(run_child("fds", &env, None), "unknown=3 unknown=4");
+}
+
+#[test]
+fn listen_fds_requires_matching_pid() {
+    let env = [("LISTEN_PID", "1"), ("LISTEN_FDS", "2"), ("LISTEN_FDNAMES", "a:b")];
+    assert_eq!(run_child("fds", &env, None), "");
+}
+
+#[test]
+fn listen_fds_rejects_out_of_range_counts() {
+    for count in ["2147483647", "-1"] {
+        let env = [("LISTEN_PID", "self"), ("LISTEN_FDS", count)];
+        assert!(run_child("fds", &env, None).starts_with("error: InvalidFormat"), "{}", count);
+    }
+}
+
+#[test]
+fn listen_sockets_maps_declared_names() {
+    let dir = TempDir::new("sockets-declared");
+    let bundle = socket_bundle(&dir, unix_sockets(&dir));
+
+    // Ohne LISTEN_FDNAMES gilt die Deklarationsreihenfolge
+    let env = [("LISTEN_PID", "self"), ("LISTEN_FDS", "2")];
+    assert_eq!(run_child("bundle", &env, Some(bundle.path())), "ctl=3 web=4");
+
+    let env = [("LISTEN_PID", "self"), ("LISTEN_FDS", "2"), ("LISTEN_FDNAMES", "web:other")];
+    assert_eq!(run_child("bundle", &env, Some(bundle.path())), "web=3");
+}
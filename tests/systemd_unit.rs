@@ -0,0 +1,171 @@
+//! Vergleicht erzeugte systemd-Units mit den Snapshots unter `tests/snapshots/`.
+//!
+//! Nach gewollten Änderungen am Generator neu erzeugen mit:
+//! `BUNDLE_UPDATE_SNAPSHOTS=1 cargo test --test systemd_unit`
+
+use std::path::Path;
+
+use bundle::{systemd_service_unit, systemd_socket_units, BundleError, BundleInfoConfigFile, SystemdScope};
+
+fn info(json: &str) -> BundleInfoConfigFile {
+    serde_json::from_str(json).expect("Info.json des Tests ist ungültig")
+}
+
+fn assert_snapshot(name: &str, generated: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots").join(name);
+    if std::env::var_os("BUNDLE_UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, generated).expect("Snapshot konnte nicht geschrieben werden");
+        return;
+    }
+    let committed = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        committed == generated,
+        "{} ist veraltet; mit BUNDLE_UPDATE_SNAPSHOTS=1 neu erzeugen\n\n{}",
+        name,
+        generated
+    );
+}
+
+const SANDBOXED: &str = r#"{
+    "name": "Echo 100%",
+    "identifier": "org.example.echo",
+    "entry_point": "bin/echod",
+    "metadata": {},
+    "icons": {"icon_16": "", "icon_32": "", "icon_128": "", "launch_screen": ""},
+    "platforms": ["linux"],
+    "minimum_system_version": "1.0",
+    "device_family": [],
+    "entitlements": ["camera", "microphone", "location"],
+    "url_schemes": [],
+    "app_services": {"background_modes": ["audio", "fetch", "unknown"]},
+    "security": {
+        "app_sandbox": true,
+        "app_transport_security": {"allows_insecure_http": false, "exception_domains": {}},
+        "code_signature": {"team_id": "TEAM1", "entitlements_file": ""}
+    },
+    "fibyos": {"document_types": []},
+    "launch": {
+        "arguments": ["--data=${BUNDLE_DATA_DIR}", "--greeting", "hello \"world\"", "$$HOME"],
+        "environment": {"ECHO_ROOT": "${BUNDLE_CONTENT_PATH}/share", "ECHO_LEVEL": "5%"},
+        "working_directory": "var"
+    },
+    "service": {
+        "restart": "always",
+        "restart_delay_seconds": 3,
+        "start_timeout_seconds": 30,
+        "health_check": {"socket": "127.0.0.1:7"},
+        "after": ["org.example.log"],
+        "requires": ["org.example.store"],
+        "sockets": [
+            {"name": "control", "path": "/run/${BUNDLE_IDENTIFIER}/control.sock"},
+            {"name": "http", "port": 8080, "address": "::1"}
+        ]
+    }
+}"#;
+
+const UNSANDBOXED: &str = r#"{
+    "name": "Plain",
+    "identifier": "org.example.plain",
+    "entry_point": "plain",
+    "metadata": {},
+    "icons": {"icon_16": "", "icon_32": "", "icon_128": "", "launch_screen": ""},
+    "platforms": ["linux"],
+    "minimum_system_version": "1.0",
+    "device_family": [],
+    "entitlements": [],
+    "url_schemes": [],
+    "app_services": {"background_modes": []},
+    "security": {
+        "app_sandbox": false,
+        "app_transport_security": {"allows_insecure_http": false, "exception_domains": {}},
+        "code_signature": {"team_id": "TEAM1", "entitlements_file": ""}
+    },
+    "fibyos": {"document_types": []}
+}"#;
+
+#[test]
+fn sandboxed_system_unit() {
+    let unit = systemd_service_unit(
+        &info(SANDBOXED),
+        Path::new("/usr/share/bundles/Echo.serviced"),
+        SystemdScope::System,
+    )
+    .unwrap();
+    assert_snapshot("sandboxed_system.service", &unit);
+}
+
+#[test]
+fn unsandboxed_user_unit() {
+    let unit = systemd_service_unit(
+        &info(UNSANDBOXED),
+        Path::new("/home/user/.local/share/bundles/Plain.serviced"),
+        SystemdScope::User,
+    )
+    .unwrap();
+    assert_snapshot("unsandboxed_user.service", &unit);
+}
+
+#[test]
+fn socket_units() {
+    let units = systemd_socket_units(&info(SANDBOXED), Path::new("/usr/share/bundles/Echo.serviced")).unwrap();
+    let text: String = units
+        .iter()
+        .map(|(name, unit)| format!("### {}\n{}", name, unit))
+        .collect();
+    assert_snapshot("sockets.socket", &text);
+    assert!(systemd_socket_units(&info(UNSANDBOXED), Path::new("/usr/share/bundles/Plain.serviced")).unwrap().is_empty());
+}
+
+#[test]
+fn socket_paths_must_not_use_data_directories() {
+    for placeholder in ["BUNDLE_DATA_DIR", "BUNDLE_CACHE_DIR", "BUNDLE_CONFIG_DIR"] {
+        let mut info = info(SANDBOXED);
+        info.service.as_mut().unwrap().sockets[0].path = Some(format!("${{{}}}/control.sock", placeholder));
+        let result = systemd_socket_units(&info, Path::new("/usr/share/bundles/Echo.serviced"));
+        assert!(matches!(result, Err(BundleError::InvalidFormat(_))), "{}", placeholder);
+    }
+}
+
+#[test]
+fn escaping_entry_points_are_rejected() {
+    for entry_point in ["../../../bin/sh", "/bin/sh", "bin/../../x", ""] {
+        let mut info = info(SANDBOXED);
+        info.entry_point = entry_point.into();
+        let result = systemd_service_unit(&info, Path::new("/usr/share/bundles/Echo.serviced"), SystemdScope::System);
+        assert!(matches!(result, Err(BundleError::InvalidFormat(_))), "{:?}: {:?}", entry_point, result);
+    }
+}
+
+type Mutation = fn(&mut BundleInfoConfigFile, &str);
+
+#[test]
+fn control_characters_are_rejected() {
+    let injected = "x\n[Service]\nExecStartPre=/bin/sh -c evil";
+    let mutations: [(&str, Mutation); 7] = [
+        ("name", |info, value| info.name = value.into()),
+        ("entry_point", |info, value| info.entry_point = value.into()),
+        ("argument", |info, value| info.launch.arguments.push(value.into())),
+        ("environment key", |info, value| {
+            info.launch.environment.insert(value.into(), "1".into());
+        }),
+        ("environment value", |info, value| {
+            info.launch.environment.insert("ECHO_EXTRA".into(), value.into());
+        }),
+        ("working_directory", |info, value| info.launch.working_directory = Some(value.into())),
+        ("carriage return", |info, _| info.launch.arguments.push("a\rb".into())),
+    ];
+    let bundle = Path::new("/usr/share/bundles/Echo.serviced");
+    for (what, mutate) in mutations {
+        let mut info = info(SANDBOXED);
+        mutate(&mut info, injected);
+        let result = systemd_service_unit(&info, bundle, SystemdScope::System);
+        assert!(matches!(result, Err(BundleError::InvalidFormat(_))), "{}: {:?}", what, result);
+    }
+
+    let mut sockets = info(SANDBOXED);
+    sockets.service.as_mut().unwrap().sockets[1].address = Some("::1\nListenStream=0.0.0.0:22".into());
+    assert!(matches!(systemd_socket_units(&sockets, bundle), Err(BundleError::InvalidFormat(_))));
+
+    let result = systemd_service_unit(&info(SANDBOXED), Path::new("/tmp/Echo\n.serviced"), SystemdScope::User);
+    assert!(matches!(result, Err(BundleError::InvalidFormat(_))));
+}
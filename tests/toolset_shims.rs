@@ -0,0 +1,131 @@
+//! Shims für Toolset-Befehle: nur eigene Einträge werden angelegt, ersetzt
+//! oder entfernt; fremde Dateien und Shims anderer Toolsets bleiben stehen.
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+use bundle::{Bundle, BundleError, BundleKind, Shim, ShimDirectory, ShimKind, ShimReport};
+use common::{create_bundle_with, isolate_xdg, write_executable, TempDir};
+use serde_json::{json, Value};
+
+/// Toolset mit den Befehlen `commands`, jeweils unter `bin/<name>`.
+fn toolset(dir: &TempDir, identifier: &str, commands: &[&str]) -> Bundle {
+    let executables: serde_json::Map<String, Value> = commands
+        .iter()
+        .map(|name| (name.to_string(), json!({ "path": format!("bin/{}", name) })))
+        .collect();
+    let path = create_bundle_with(&dir.path.join("bundles"), BundleKind::Toolset, identifier, |info| {
+        info["executables"] = Value::Object(executables);
+    });
+    for name in commands {
+        write_executable(&path.join("Content/bin").join(name), "#!/bin/sh\necho \"$BUNDLE_IDENTIFIER $*\"\n");
+    }
+    Bundle::open(&path).unwrap()
+}
+
+fn names(shims: &[Shim]) -> Vec<&str> {
+    shims.iter().map(|shim| shim.name.as_str()).collect()
+}
+
+fn summary(report: &ShimReport) -> (Vec<&str>, Vec<&str>, Vec<&str>) {
+    (names(&report.installed), names(&report.unchanged), names(&report.removed))
+}
+
+fn bin(dir: &TempDir) -> PathBuf {
+    dir.path.join("bin")
+}
+
+#[test]
+fn install_is_idempotent_and_removes_stale_shims() {
+    let dir = TempDir::new("shims-install");
+    let shims = ShimDirectory::new(bin(&dir), ShimKind::Symlink);
+    let tools = toolset(&dir, "org.example.tools", &["alpha", "beta"]);
+
+    assert_eq!(summary(&shims.install(&tools).unwrap()), (vec!["alpha", "beta"], vec![], vec![]));
+    assert_eq!(fs::read_link(bin(&dir).join("alpha")).unwrap(), tools.content_path().join("bin/alpha"));
+    assert_eq!(summary(&shims.install(&tools).unwrap()), (vec![], vec!["alpha", "beta"], vec![]));
+
+    // `beta` fällt weg
+    let tools = toolset(&dir, "org.example.tools", &["alpha"]);
+    assert_eq!(summary(&shims.install(&tools).unwrap()), (vec![], vec!["alpha"], vec!["beta"]));
+    assert!(fs::symlink_metadata(bin(&dir).join("beta")).is_err());
+
+    let report = shims.uninstall(tools.path()).unwrap();
+    assert_eq!(summary(&report), (vec![], vec![], vec!["alpha"]));
+    assert!(shims.shims().unwrap().is_empty());
+}
+
+#[test]
+fn foreign_files_and_other_toolsets_are_conflicts() {
+    let dir = TempDir::new("shims-conflicts");
+    let shims = ShimDirectory::new(bin(&dir), ShimKind::Symlink);
+    fs::create_dir_all(bin(&dir)).unwrap();
+    fs::write(bin(&dir).join("alpha"), "mine").unwrap();
+    let first = toolset(&dir, "org.example.first", &["alpha", "beta"]);
+    let second = toolset(&dir, "org.example.second", &["beta", "gamma"]);
+
+    let report = shims.install(&first).unwrap();
+    assert_eq!(names(&report.installed), ["beta"]);
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].name, "alpha");
+    assert_eq!(report.conflicts[0].winner, None);
+    assert_eq!(fs::read_to_string(bin(&dir).join("alpha")).unwrap(), "mine");
+
+    let report = shims.install(&second).unwrap();
+    assert_eq!(names(&report.installed), ["gamma"]);
+    assert_eq!(report.conflicts[0].name, "beta");
+    assert_eq!(report.conflicts[0].winner.as_deref(), Some(first.path()));
+
+    // Deinstallation eines Toolsets lässt die Shims des anderen und fremde Dateien stehen
+    assert_eq!(names(&shims.uninstall(second.path()).unwrap().removed), ["gamma"]);
+    assert_eq!(fs::read_to_string(bin(&dir).join("alpha")).unwrap(), "mine");
+    assert!(bin(&dir).join("beta").exists());
+}
+
+#[test]
+fn sync_keeps_exactly_the_given_toolsets() {
+    let dir = TempDir::new("shims-sync");
+    let shims = ShimDirectory::new(bin(&dir), ShimKind::Symlink);
+    let first = toolset(&dir, "org.example.first", &["shared", "one"]);
+    let second = toolset(&dir, "org.example.second", &["shared", "two"]);
+
+    let report = shims.sync(&[&second, &first]).unwrap();
+    assert_eq!(names(&report.installed), ["one", "shared", "two"]);
+    assert_eq!(report.conflicts[0].losers, [first.path().to_path_buf()]);
+    assert!(fs::read_link(bin(&dir).join("shared")).unwrap().starts_with(second.path()));
+
+    let report = shims.sync(&[&first]).unwrap();
+    assert_eq!(summary(&report), (vec!["shared"], vec!["one"], vec!["shared", "two"]));
+    assert!(fs::read_link(bin(&dir).join("shared")).unwrap().starts_with(first.path()));
+}
+
+#[test]
+fn script_shim_runs_command_with_bundle_environment() {
+    isolate_xdg();
+    let dir = TempDir::new("shims-script");
+    let shims = ShimDirectory::new(bin(&dir), ShimKind::Script);
+    let tools = toolset(&dir, "org.example.scripted", &["hello"]);
+
+    shims.install(&tools).unwrap();
+    assert_eq!(names(&shims.shims().unwrap()), ["hello"]);
+    let output = Command::new(bin(&dir).join("hello")).arg("world").output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "org.example.scripted world\n");
+    assert_eq!(names(&shims.install(&tools).unwrap().unchanged), ["hello"]);
+}
+
+#[test]
+fn only_toolsets_get_shims() {
+    let dir = TempDir::new("shims-kind");
+    let shims = ShimDirectory::new(bin(&dir), ShimKind::Symlink);
+    let app = create_bundle_with(&dir.path, BundleKind::App, "org.example.app", |info| {
+        info["executables"] = json!({ "app": { "path": "bin/app" } });
+    });
+    let app = Bundle::open(&app).unwrap();
+
+    assert!(matches!(shims.install(&app), Err(BundleError::InvalidFormat(_))));
+    assert!(matches!(shims.sync(&[&app]), Err(BundleError::InvalidFormat(_))));
+    assert!(!bin(&dir).exists());
+}
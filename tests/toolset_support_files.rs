@@ -0,0 +1,127 @@
+//! Man-Pages und Shell-Completions von Toolsets: Erkennung im Bundle und
+//! Verlinkung in die Zielverzeichnisse, ohne fremde Dateien anzufassen.
+
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use bundle::{Bundle, BundleError, BundleKind, Shell, SupportFileDirectories};
+use common::{create_bundle_with, TempDir};
+
+/// Toolset mit den Dateien `files` unter `Content/Resources/`.
+fn toolset(dir: &TempDir, identifier: &str, files: &[&str]) -> Bundle {
+    let path = create_bundle_with(&dir.path.join("bundles"), BundleKind::Toolset, identifier, |_| {});
+    let resources = path.join("Content/Resources");
+    let _ = fs::remove_dir_all(&resources);
+    for file in files {
+        let file = resources.join(file);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, "").unwrap();
+    }
+    Bundle::open(&path).unwrap()
+}
+
+fn targets(dir: &TempDir) -> SupportFileDirectories {
+    let share = dir.path.join("share");
+    SupportFileDirectories {
+        man: share.join("man"),
+        bash: share.join("bash"),
+        zsh: share.join("zsh"),
+        fish: share.join("fish"),
+    }
+}
+
+fn relative(dir: &TempDir, paths: &[PathBuf]) -> Vec<String> {
+    let share = dir.path.join("share");
+    paths.iter().map(|p| p.strip_prefix(&share).unwrap().display().to_string()).collect()
+}
+
+const FILES: &[&str] = &[
+    "man/man1/tool.1",
+    "man/man5/tool.conf.5.gz",
+    "man/manual.txt",
+    "completions/bash/tool",
+    "completions/zsh/_tool",
+    "completions/zsh/not-a-completion",
+    "completions/fish/tool.fish",
+    "completions/fish/.hidden.fish",
+];
+
+#[test]
+fn support_files_are_discovered_by_convention() {
+    let dir = TempDir::new("support-discover");
+    let tools = toolset(&dir, "org.example.tools", FILES);
+
+    let pages: Vec<_> = tools.man_pages().unwrap().into_iter().map(|p| (p.section, p.file_name)).collect();
+    assert_eq!(pages, [("1".to_string(), "tool.1".to_string()), ("5".to_string(), "tool.conf.5.gz".to_string())]);
+    let completions: Vec<_> = tools.shell_completions().unwrap().into_iter().map(|c| (c.shell, c.command)).collect();
+    assert_eq!(
+        completions,
+        [(Shell::Bash, "tool".to_string()), (Shell::Zsh, "tool".to_string()), (Shell::Fish, "tool".to_string())]
+    );
+}
+
+#[test]
+fn install_is_idempotent_and_removes_stale_links() {
+    let dir = TempDir::new("support-install");
+    let targets = targets(&dir);
+    let tools = toolset(&dir, "org.example.tools", FILES);
+
+    let report = targets.install(&tools).unwrap();
+    assert_eq!(
+        relative(&dir, &report.linked),
+        ["man/man1/tool.1", "man/man5/tool.conf.5.gz", "bash/tool", "zsh/_tool", "fish/tool.fish"]
+    );
+    assert_eq!(
+        fs::read_link(dir.path.join("share/zsh/_tool")).unwrap(),
+        tools.resources_path().join("completions/zsh/_tool")
+    );
+    let report = targets.install(&tools).unwrap();
+    assert!(report.linked.is_empty());
+    assert_eq!(report.unchanged.len(), 5);
+
+    // Die Man-Page in Abschnitt 5 und die fish-Completion fallen weg
+    let tools = toolset(&dir, "org.example.tools", &["man/man1/tool.1", "completions/bash/tool", "completions/zsh/_tool"]);
+    let report = targets.install(&tools).unwrap();
+    assert_eq!(relative(&dir, &report.removed), ["fish/tool.fish", "man/man5/tool.conf.5.gz"]);
+    assert!(fs::symlink_metadata(dir.path.join("share/fish/tool.fish")).is_err());
+
+    let report = targets.uninstall(tools.path()).unwrap();
+    assert_eq!(relative(&dir, &report.removed), ["bash/tool", "man/man1/tool.1", "zsh/_tool"]);
+    assert!(fs::read_dir(dir.path.join("share/bash")).unwrap().next().is_none());
+}
+
+#[test]
+fn foreign_files_and_other_toolsets_are_conflicts() {
+    let dir = TempDir::new("support-conflicts");
+    let targets = targets(&dir);
+    fs::create_dir_all(&targets.bash).unwrap();
+    fs::write(targets.bash.join("tool"), "# mine").unwrap();
+    let first = toolset(&dir, "org.example.first", &["completions/bash/tool", "man/man1/tool.1"]);
+    let second = toolset(&dir, "org.example.second", &["man/man1/tool.1"]);
+
+    let report = targets.install(&first).unwrap();
+    assert_eq!(relative(&dir, &report.linked), ["man/man1/tool.1"]);
+    assert_eq!(report.conflicts.len(), 1);
+    assert_eq!(report.conflicts[0].owner, None);
+    assert_eq!(fs::read_to_string(targets.bash.join("tool")).unwrap(), "# mine");
+
+    let report = targets.install(&second).unwrap();
+    assert!(report.linked.is_empty());
+    assert_eq!(report.conflicts[0].owner.as_deref(), Some(first.path()));
+    assert!(fs::read_link(targets.man.join("man1/tool.1")).unwrap().starts_with(first.path()));
+
+    // Deinstallieren des unterlegenen Toolsets entfernt nichts
+    assert!(targets.uninstall(second.path()).unwrap().removed.is_empty());
+    assert_eq!(fs::read_to_string(targets.bash.join("tool")).unwrap(), "# mine");
+}
+
+#[test]
+fn only_toolsets_are_linked() {
+    let dir = TempDir::new("support-kind");
+    let app = create_bundle_with(&dir.path, BundleKind::App, "org.example.app", |_| {});
+    let app = Bundle::open(&app).unwrap();
+    assert!(matches!(targets(&dir).install(&app), Err(BundleError::InvalidFormat(_))));
+    assert!(!dir.path.join("share").exists());
+}
@@ -0,0 +1,97 @@
+//! URL-Scheme-Handler: Rangfolge über Suchstufen, Benutzereinstellung und Konflikte.
+
+mod common;
+
+use std::path::Path;
+
+use bundle::{
+    url_scheme, BundleError, BundleKind, BundleRegistry, HandlerPreferences, SearchRoot, SearchScope, UrlSchemeConflict,
+    UrlSchemeHandlers,
+};
+use common::{create_bundle_with, TempDir};
+use serde_json::json;
+
+fn declare_schemes(root: &Path, identifier: &str, schemes: &[&str]) {
+    let schemes: Vec<_> = schemes.iter().map(|s| json!({ "scheme": s, "description": "" })).collect();
+    create_bundle_with(root, BundleKind::App, identifier, |info| info["url_schemes"] = json!(schemes));
+}
+
+/// `org.example.system` (System) sowie `org.example.b` und `org.example.a` (User)
+/// deklarieren `web`; nur `org.example.a` deklariert `mail`, zweimal.
+fn registry(dir: &TempDir) -> BundleRegistry {
+    let system = dir.path.join("system");
+    let user = dir.path.join("user");
+    declare_schemes(&system, "org.example.system", &["web"]);
+    declare_schemes(&user, "org.example.b", &["WEB"]);
+    declare_schemes(&user, "org.example.a", &["web", "mail", "Mail"]);
+    BundleRegistry::scan(vec![SearchRoot::new(SearchScope::System, system), SearchRoot::new(SearchScope::User, user)])
+}
+
+fn identifiers(handlers: &UrlSchemeHandlers<'_>) -> Vec<String> {
+    handlers.handlers.iter().map(|b| b.identifier().to_string()).collect()
+}
+
+#[test]
+fn scheme_is_parsed_per_rfc_3986() {
+    assert_eq!(url_scheme("HTTPS://example.org").as_deref(), Some("https"));
+    assert_eq!(url_scheme("  git+ssh://host/repo").as_deref(), Some("git+ssh"));
+    assert_eq!(url_scheme("mailto:someone@example.org").as_deref(), Some("mailto"));
+    for url in ["", "example.org", "1http://x", "://x", "ht tp://x"] {
+        assert_eq!(url_scheme(url), None, "{:?}", url);
+    }
+}
+
+#[test]
+fn handlers_are_ranked_by_scope_then_identifier() {
+    let dir = TempDir::new("url-handlers-rank");
+    let registry = registry(&dir);
+    let handlers = registry.handlers_for_url("Web://page", &HandlerPreferences::default()).unwrap();
+
+    assert_eq!(handlers.scheme, "web");
+    assert_eq!(identifiers(&handlers), ["org.example.a", "org.example.b", "org.example.system"]);
+    assert!(!handlers.preferred);
+    assert!(handlers.is_conflict());
+    assert!(registry.handlers_for_scheme("ftp", &HandlerPreferences::default()).default_handler().is_none());
+}
+
+#[test]
+fn preference_moves_handler_to_front() {
+    let dir = TempDir::new("url-handlers-preference");
+    let registry = registry(&dir);
+    let mut preferences = HandlerPreferences::default();
+    preferences.set_url_handler("WEB", "org.example.system");
+
+    let handlers = registry.handlers_for_scheme("web", &preferences);
+    assert_eq!(identifiers(&handlers), ["org.example.system", "org.example.a", "org.example.b"]);
+    assert!(handlers.preferred);
+
+    // Eine Einstellung für ein Bundle, das das Scheme nicht deklariert, wird ignoriert
+    preferences.set_url_handler("mail", "org.example.b");
+    let handlers = registry.handlers_for_scheme("mail", &preferences);
+    assert_eq!(identifiers(&handlers), ["org.example.a"]);
+    assert!(!handlers.preferred);
+}
+
+#[test]
+fn conflicts_count_each_bundle_once() {
+    let dir = TempDir::new("url-handlers-conflicts");
+    let registry = registry(&dir);
+
+    assert_eq!(
+        registry.url_scheme_conflicts(),
+        [UrlSchemeConflict {
+            scheme: "web".into(),
+            identifiers: vec!["org.example.a".into(), "org.example.b".into(), "org.example.system".into()],
+        }]
+    );
+}
+
+#[test]
+fn url_without_scheme_is_rejected() {
+    let dir = TempDir::new("url-handlers-invalid");
+    let registry = registry(&dir);
+    assert!(matches!(
+        registry.handlers_for_url("example.org/page", &HandlerPreferences::default()),
+        Err(BundleError::InvalidFormat(_))
+    ));
+}